@@ -0,0 +1,92 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use growthbook_rust_sdk::client::{GrowthBookClient, GrowthBookClientTrait};
+use growthbook_rust_sdk::condition::model::Condition;
+use growthbook_rust_sdk::model_public::{GrowthBookAttribute, GrowthBookAttributes};
+use growthbook_rust_sdk::source::static_source::StaticSource;
+use serde_json::{json, Map, Value};
+
+fn nested(depth: usize) -> Value {
+    (0..depth).fold(json!({ "leaf": "value" }), |inner, level| {
+        let mut map = Map::new();
+        for index in 0..10 {
+            map.insert(format!("sibling_{level}_{index}"), json!(index));
+        }
+        map.insert(format!("level_{level}"), inner);
+        Value::Object(map)
+    })
+}
+
+fn attributes(
+    width: usize,
+    depth: usize,
+) -> Value {
+    let mut map = Map::new();
+    for index in 0..width {
+        map.insert(format!("attribute_{index}"), json!(format!("value_{index}")));
+    }
+    map.insert(String::from("nested"), nested(depth));
+    Value::Object(map)
+}
+
+fn deep_path(depth: usize) -> String {
+    let levels = (0..depth).rev().map(|level| format!("level_{level}")).collect::<Vec<_>>().join(".");
+    format!("nested.{levels}.leaf")
+}
+
+fn lookup(c: &mut Criterion) {
+    let depth = 8;
+    let path = deep_path(depth);
+    let value = attributes(500, depth);
+    let vec = GrowthBookAttribute::from(value.clone()).expect("Failed to create attributes");
+    let container = GrowthBookAttributes::from(value).expect("Failed to create attributes");
+
+    c.bench_function("attributes_build_500_depth_8", |b| b.iter(|| GrowthBookAttributes::new(black_box(vec.clone()))));
+    c.bench_function("attributes_get_deep_path", |b| b.iter(|| container.get(black_box(&path))));
+    c.bench_function("attributes_get_top_level", |b| b.iter(|| container.get(black_box("attribute_499"))));
+    c.bench_function("attributes_get_missing", |b| b.iter(|| container.get(black_box("nested.level_7.missing.leaf"))));
+}
+
+fn condition(c: &mut Criterion) {
+    let depth = 8;
+    let path = deep_path(depth);
+    let value = attributes(500, depth);
+    let container = GrowthBookAttributes::from(value).expect("Failed to create attributes");
+    let mut map = Map::new();
+    for index in (0..500).step_by(25) {
+        map.insert(format!("attribute_{index}"), json!({ "$eq": format!("value_{index}") }));
+    }
+    map.insert(path, json!({ "$in": ["value", "other"] }));
+    let condition = Condition::new(Value::Object(map)).expect("Failed to create condition");
+
+    c.bench_function("condition_matches_21_keys", |b| b.iter(|| condition.matches(black_box(&container))));
+}
+
+fn client(c: &mut Criterion) {
+    let depth = 8;
+    let path = deep_path(depth);
+    let value = attributes(500, depth);
+    let vec = GrowthBookAttribute::from(value.clone()).expect("Failed to create attributes");
+    let container = GrowthBookAttributes::from(value).expect("Failed to create attributes");
+    let mut features = Map::new();
+    for index in 0..200 {
+        features.insert(
+            format!("flag_{index}"),
+            json!({
+                "defaultValue": false,
+                "rules": [
+                    { "condition": { format!("attribute_{index}"): format!("value_{index}") }, "force": true },
+                    { "condition": { path.clone(): { "$in": ["value"] } }, "force": true }
+                ]
+            }),
+        );
+    }
+    let source = StaticSource::from_value(json!({ "features": features })).expect("Failed to create source");
+    let runtime = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+    let client = runtime.block_on(GrowthBookClient::from_source(source).build()).expect("Failed to create client");
+
+    c.bench_function("client_is_on_vec", |b| b.iter(|| client.is_on(black_box("flag_199"), Some(vec.clone()))));
+    c.bench_function("client_is_on_for_container", |b| b.iter(|| client.is_on_for(black_box("flag_199"), &container)));
+}
+
+criterion_group!(benches, lookup, condition, client);
+criterion_main!(benches);
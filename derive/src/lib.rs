@@ -0,0 +1,108 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, LitStr};
+
+#[proc_macro_derive(GrowthBookAttributes, attributes(growthbook))]
+pub fn derive_growthbook_attributes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    rename: Option<String>,
+    skip: bool,
+    flatten: bool,
+    secure: bool,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(name, "GrowthBookAttributes can only be derived for structs with named fields")),
+        },
+        _ => return Err(Error::new_spanned(name, "GrowthBookAttributes can only be derived for structs")),
+    };
+
+    let mut attributes = vec![];
+    let mut secure_keys = vec![];
+    for field in fields {
+        let options = field_options(field)?;
+        if options.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let key = options.rename.unwrap_or_else(|| ident.to_string());
+        let value = if options.flatten {
+            quote! {{
+                let attributes = ::growthbook_rust_sdk::model_public::ToGrowthBookAttributes::to_growthbook_attributes(&self.#ident);
+                if attributes.is_empty() {
+                    ::growthbook_rust_sdk::model_public::GrowthBookAttributeValue::Empty
+                } else {
+                    ::growthbook_rust_sdk::model_public::GrowthBookAttributeValue::Object(attributes)
+                }
+            }}
+        } else {
+            quote! {
+                ::growthbook_rust_sdk::model_public::GrowthBookAttributeValue::from(::std::clone::Clone::clone(&self.#ident))
+            }
+        };
+        attributes.push(quote! {
+            ::growthbook_rust_sdk::model_public::GrowthBookAttribute::new(::std::string::String::from(#key), #value)
+        });
+        if options.secure {
+            secure_keys.push(quote! { keys.push(::std::string::String::from(#key)); });
+        }
+        if options.flatten {
+            secure_keys.push(quote! {
+                for nested in <#ty as ::growthbook_rust_sdk::model_public::ToGrowthBookAttributes>::secure_attribute_keys() {
+                    keys.push(::std::format!("{}.{}", #key, nested));
+                }
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::growthbook_rust_sdk::model_public::ToGrowthBookAttributes for #name #type_generics #where_clause {
+            fn to_growthbook_attributes(&self) -> ::std::vec::Vec<::growthbook_rust_sdk::model_public::GrowthBookAttribute> {
+                ::std::vec![#(#attributes),*]
+            }
+
+            fn secure_attribute_keys() -> ::std::vec::Vec<::std::string::String> {
+                #[allow(unused_mut)]
+                let mut keys = ::std::vec::Vec::new();
+                #(#secure_keys)*
+                keys
+            }
+        }
+    })
+}
+
+fn field_options(field: &Field) -> Result<FieldOptions, Error> {
+    let mut options = FieldOptions::default();
+    for attribute in field.attrs.iter().filter(|it| it.path().is_ident("growthbook")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+                Ok(())
+            } else if meta.path.is_ident("flatten") {
+                options.flatten = true;
+                Ok(())
+            } else if meta.path.is_ident("secure") {
+                options.secure = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported growthbook attribute, expected `rename`, `skip`, `flatten` or `secure`"))
+            }
+        })?;
+    }
+    Ok(options)
+}
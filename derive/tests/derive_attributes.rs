@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use growthbook_rust_sdk::model_public::{GrowthBookAttribute, GrowthBookAttributes, ToGrowthBookAttributes};
+use serde_json::json;
+
+#[derive(GrowthBookAttributes)]
+struct User {
+    id: String,
+    #[growthbook(rename = "accountAge")]
+    account_age: u32,
+    #[growthbook(secure)]
+    email: String,
+    beta: bool,
+    tags: Vec<String>,
+    referrer: Option<String>,
+    #[growthbook(skip)]
+    #[allow(dead_code)]
+    password: String,
+    #[growthbook(flatten)]
+    tenant: Tenant,
+}
+
+#[derive(GrowthBookAttributes)]
+struct Tenant {
+    id: i64,
+    #[growthbook(secure, rename = "ownerEmail")]
+    owner_email: String,
+    limits: HashMap<String, i64>,
+}
+
+fn user() -> User {
+    User {
+        id: String::from("123"),
+        account_age: 30,
+        email: String::from("user@example.com"),
+        beta: true,
+        tags: vec![String::from("a"), String::from("b")],
+        referrer: None,
+        password: String::from("secret"),
+        tenant: Tenant {
+            id: 7,
+            owner_email: String::from("owner@example.com"),
+            limits: HashMap::from([(String::from("seats"), 10)]),
+        },
+    }
+}
+
+#[test]
+fn should_convert_struct_into_attributes() -> Result<(), Box<dyn std::error::Error>> {
+    let attributes = user().to_growthbook_attributes();
+
+    assert_eq!(
+        GrowthBookAttribute::from(json!({
+            "id": "123",
+            "accountAge": 30,
+            "email": "user@example.com",
+            "beta": true,
+            "tags": ["a", "b"],
+            "referrer": null,
+            "tenant": { "id": 7, "ownerEmail": "owner@example.com", "limits": { "seats": 10 } },
+        }))?,
+        attributes
+    );
+
+    Ok(())
+}
+
+#[test]
+fn should_list_secure_keys_including_nested_ones() {
+    assert_eq!(vec![String::from("email"), String::from("tenant.ownerEmail")], User::secure_attribute_keys());
+    assert_eq!(vec![String::from("ownerEmail")], Tenant::secure_attribute_keys());
+}
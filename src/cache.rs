@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use crate::dto::GrowthBookResponse;
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+
+#[derive(Clone, Debug)]
+pub struct PayloadCache {
+    path: PathBuf,
+    max_age: Option<Duration>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedPayload {
+    saved_at: DateTime<Utc>,
+    payload: GrowthBookResponse,
+}
+
+impl PayloadCache {
+    pub fn new(
+        path: &Path,
+        max_age: Option<Duration>,
+    ) -> Self {
+        PayloadCache { path: path.to_path_buf(), max_age }
+    }
+
+    pub async fn save(
+        &self,
+        payload: &GrowthBookResponse,
+    ) -> Result<(), GrowthbookError> {
+        let cached = CachedPayload {
+            saved_at: Utc::now(),
+            payload: payload.clone(),
+        };
+        let contents = serde_json::to_vec(&cached)?;
+        let mut temporary_path = self.path.clone().into_os_string();
+        temporary_path.push(".tmp");
+        let mut file = tokio::fs::File::create(&temporary_path).await.map_err(|e| cache_error("create", &e))?;
+        file.write_all(&contents).await.map_err(|e| cache_error("write", &e))?;
+        // the contents must reach the disk before the rename, or a crash can leave an empty cache behind
+        file.sync_all().await.map_err(|e| cache_error("sync", &e))?;
+        drop(file);
+        tokio::fs::rename(&temporary_path, &self.path).await.map_err(|e| cache_error("rename", &e))
+    }
+
+    pub async fn load(&self) -> Option<(GrowthBookResponse, DateTime<Utc>)> {
+        let contents = match tokio::fs::read(&self.path).await {
+            Ok(it) => it,
+            Err(e) => {
+                warn!("[growthbook-sdk] Failed to read payload cache {:?}: {}", self.path, e);
+                return None;
+            },
+        };
+        let cached = match serde_json::from_slice::<CachedPayload>(&contents) {
+            Ok(it) => it,
+            Err(e) => {
+                warn!("[growthbook-sdk] Ignoring corrupted payload cache {:?}: {}", self.path, e);
+                return None;
+            },
+        };
+        if let Some(max_age) = self.max_age {
+            let age = Utc::now().signed_duration_since(cached.saved_at).to_std().unwrap_or_default();
+            if age > max_age {
+                warn!("[growthbook-sdk] Ignoring payload cache {:?} older than {:?}", self.path, max_age);
+                return None;
+            }
+        }
+        Some((cached.payload, cached.saved_at))
+    }
+}
+
+fn cache_error(
+    operation: &str,
+    error: &std::io::Error,
+) -> GrowthbookError {
+    GrowthbookError::new(GrowthbookErrorCode::PayloadCache, &format!("Failed to {operation} payload cache: {error}"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::cache::PayloadCache;
+    use crate::dto::GrowthBookResponse;
+
+    fn payload() -> GrowthBookResponse {
+        serde_json::from_value(json!({
+            "features": {
+                "force-flag": { "defaultValue": false, "rules": [{ "id": "fr_1", "condition": { "country": "BR" }, "force": true }] },
+                "rollout-flag": { "defaultValue": false, "rules": [{ "force": true, "coverage": 0.5, "hashAttribute": "id" }] },
+                "experiment-flag": { "defaultValue": "a", "rules": [{ "key": "exp", "variations": ["a", "b"], "weights": [0.5, 0.5] }] },
+                "parent-flag": { "defaultValue": true, "rules": [{ "parentConditions": [{ "id": "force-flag", "condition": { "value": true }, "gate": true }] }] }
+            }
+        }))
+        .expect("Failed to create payload")
+    }
+
+    fn cache_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("growthbook-cache-{}-{name}.json", std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn save_and_load_payload() -> Result<(), Box<dyn std::error::Error>> {
+        let path = cache_path("save");
+        let cache = PayloadCache::new(&path, None);
+
+        cache.save(&payload()).await?;
+        let (loaded, _) = cache.load().await.expect("Failed to load cache");
+
+        assert_eq!(payload(), loaded);
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ignore_corrupted_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let path = cache_path("corrupted");
+        std::fs::write(&path, "{ \"savedAt\": ")?;
+
+        assert!(PayloadCache::new(&path, None).load().await.is_none());
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ignore_cache_older_than_max_age() -> Result<(), Box<dyn std::error::Error>> {
+        let path = cache_path("expired");
+        std::fs::write(&path, json!({ "savedAt": "2020-01-01T00:00:00Z", "payload": { "features": {} } }).to_string())?;
+
+        assert!(PayloadCache::new(&path, Some(Duration::from_secs(60))).load().await.is_none());
+        assert!(PayloadCache::new(&path, None).load().await.is_some());
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+}
@@ -1,43 +1,66 @@
-use std::collections::HashMap;
 use std::fmt::Debug;
-use std::sync::{Arc, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 use std::time::Duration;
 
-use tokio::time::sleep;
-use tracing::error;
+use chrono::{DateTime, Utc};
+use futures_util::stream::BoxStream;
+use reqwest_middleware::ClientWithMiddleware;
+use serde::de::DeserializeOwned;
+use tokio::sync::{broadcast, oneshot, Mutex};
+use tracing::warn;
 
+use crate::cache::PayloadCache;
 use crate::env::Environment;
 use crate::error::GrowthbookError;
-use crate::gateway::GrowthbookGateway;
+use crate::explain::model::FeatureExplanation;
 use crate::growthbook::GrowthBook;
-use crate::model_public::{FeatureResult, GrowthBookAttribute};
+use crate::layer::model::PayloadLayer;
+use crate::layer::use_case::LayerStack;
+use crate::model_public::{FeatureResult, GrowthBookAttribute, GrowthBookAttributes, ToGrowthBookAttributes};
+use crate::options::{AttributeProvider, CustomOperator, EvaluationDiagnostic, EvaluationOptions, SecureAttributes};
+use crate::refresh::model::RetryPolicy;
+use crate::refresh::use_case::{save_cache, staleness_task, updated_features_task, Refresher};
+use crate::refresh::watcher::watch_file;
+use crate::source::file::FileSource;
+use crate::source::http::HttpSource;
+use crate::source::FeatureSource;
+use crate::status::{ClientStatus, FailurePolicy, Staleness, StatusTracker};
+use crate::updates::model::PayloadUpdate;
+use crate::updates::notifier::PayloadNotifier;
+use crate::watch::{feature_stream, ConfigHandle};
 
 #[derive(Debug, Clone)]
 pub struct GrowthBookClient {
     pub gb: Arc<RwLock<GrowthBook>>,
+    options: Arc<EvaluationOptions>,
+    notifier: Arc<PayloadNotifier>,
+    status: Arc<StatusTracker>,
+    refresher: Arc<Refresher>,
+    failure_policy: FailurePolicy,
+    // dropped with the last clone, which stops the file watcher
+    _watcher: Option<Arc<oneshot::Sender<()>>>,
+    // one count per clone, so the registry can tell whether a client is held outside it
+    handles: Arc<()>,
 }
 
-async fn updated_features_task(
-    growthbook_gateway: GrowthbookGateway,
-    config: Arc<RwLock<GrowthBook>>,
-    interval: Duration,
-) {
-    loop {
-        match growthbook_gateway.get_features(None).await {
-            Ok(new_config) => {
-                let mut writable_config = config.write().expect("problem to create mutex for gb data");
-                let updated_features = GrowthBook {
-                    forced_variations: new_config.forced_variations,
-                    features: new_config.features,
-                };
-                *writable_config = updated_features;
-            },
-            Err(e) => {
-                error!("[growthbook-sdk] Failed to fetch features from server: {:?}", e);
-            },
-        }
-        sleep(interval).await;
-    }
+#[derive(Debug, Clone)]
+pub struct GrowthBookClientBuilder {
+    api_url: String,
+    sdk_key: String,
+    update_interval: Option<Duration>,
+    http_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    cache_path: Option<PathBuf>,
+    cache_max_age: Option<Duration>,
+    staleness: Staleness,
+    failure_policy: FailurePolicy,
+    watch_path: Option<PathBuf>,
+    source: Option<Arc<dyn FeatureSource>>,
+    layers: Vec<PayloadLayer>,
+    // false when refreshes are scheduled by a registry
+    refresh_task: bool,
+    options: EvaluationOptions,
 }
 
 pub trait GrowthBookClientTrait: Debug + Send + Sync {
@@ -62,48 +85,417 @@ pub trait GrowthBookClientTrait: Debug + Send + Sync {
     fn total_features(&self) -> usize;
 }
 
-impl GrowthBookClient {
-    pub async fn new(
+impl GrowthBookClientBuilder {
+    pub fn new(
         api_url: &str,
         sdk_key: &str,
-        update_interval: Option<Duration>,
-        http_timeout: Option<Duration>,
-    ) -> Result<Self, GrowthbookError> {
-        let default_interval = update_interval.unwrap_or_else(|| {
+    ) -> Self {
+        GrowthBookClientBuilder {
+            api_url: String::from(api_url),
+            sdk_key: String::from(sdk_key),
+            update_interval: None,
+            http_timeout: None,
+            retry_policy: RetryPolicy::default(),
+            cache_path: None,
+            cache_max_age: None,
+            staleness: Staleness::default(),
+            failure_policy: FailurePolicy::default(),
+            watch_path: None,
+            source: None,
+            layers: vec![],
+            refresh_task: true,
+            options: EvaluationOptions::default(),
+        }
+    }
+
+    pub fn update_interval(
+        mut self,
+        update_interval: Duration,
+    ) -> Self {
+        self.update_interval = Some(update_interval);
+        self
+    }
+
+    pub fn http_timeout(
+        mut self,
+        http_timeout: Duration,
+    ) -> Self {
+        self.http_timeout = Some(http_timeout);
+        self
+    }
+
+    pub fn retry_policy(
+        mut self,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn source(
+        mut self,
+        source: impl FeatureSource + 'static,
+    ) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    pub fn layer(
+        mut self,
+        layer: PayloadLayer,
+    ) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    // only paired with a FileSource of the same path, see GrowthBookClient::from_file
+    pub(crate) fn watch_file(
+        mut self,
+        path: impl AsRef<Path>,
+    ) -> Self {
+        self.watch_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn payload_cache(
+        mut self,
+        path: impl AsRef<Path>,
+    ) -> Self {
+        self.cache_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn payload_cache_max_age(
+        mut self,
+        max_age: Duration,
+    ) -> Self {
+        self.cache_max_age = Some(max_age);
+        self
+    }
+
+    pub fn max_staleness(
+        mut self,
+        max_staleness: Duration,
+    ) -> Self {
+        self.staleness.max_staleness = Some(max_staleness);
+        self
+    }
+
+    pub fn on_stale<F>(
+        mut self,
+        hook: F,
+    ) -> Self
+    where
+        F: Fn(&ClientStatus) + Send + Sync + 'static,
+    {
+        self.staleness.on_stale = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn failure_policy(
+        mut self,
+        failure_policy: FailurePolicy,
+    ) -> Self {
+        self.failure_policy = failure_policy;
+        self
+    }
+
+    pub fn secure_attributes(
+        mut self,
+        salt: &str,
+        keys: Vec<String>,
+    ) -> Self {
+        let mut secure_attributes = SecureAttributes::new(salt, keys);
+        if let Some(previous) = self.options.secure_attributes.take() {
+            secure_attributes.keys.extend(previous.keys);
+        }
+        self.options.secure_attributes = Some(secure_attributes);
+        self
+    }
+
+    // adds the keys marked `#[growthbook(secure)]` on a derived attribute type
+    pub fn secure_attributes_for<T: ToGrowthBookAttributes>(
+        self,
+        salt: &str,
+    ) -> Self {
+        self.secure_attributes(salt, T::secure_attribute_keys())
+    }
+
+    pub fn diagnostics<F>(
+        mut self,
+        hook: F,
+    ) -> Self
+    where
+        F: Fn(&EvaluationDiagnostic) + Send + Sync + 'static,
+    {
+        self.options.diagnostics = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn custom_operator<O>(
+        mut self,
+        name: &str,
+        operator: O,
+    ) -> Self
+    where
+        O: CustomOperator + 'static,
+    {
+        self.options.add_custom_operator(name, Arc::new(operator));
+        self
+    }
+
+    pub fn attribute_provider<P>(
+        mut self,
+        name: &str,
+        provider: P,
+    ) -> Self
+    where
+        P: AttributeProvider + 'static,
+    {
+        self.options.add_attribute_provider(name, Arc::new(provider));
+        self
+    }
+
+    pub(crate) fn scheduled_by_registry(
+        mut self,
+        http_client: ClientWithMiddleware,
+    ) -> Self {
+        self.source = Some(Arc::new(HttpSource::with_client(&self.api_url, &self.sdk_key, http_client)));
+        self.refresh_task = false;
+        self
+    }
+
+    pub async fn build(self) -> Result<GrowthBookClient, GrowthbookError> {
+        let default_interval = self.update_interval.unwrap_or_else(|| {
             let seconds = Environment::u64_or_default("GB_UPDATE_INTERVAL", 60);
             Duration::from_secs(seconds)
         });
-        let default_timeout = http_timeout.unwrap_or_else(|| {
+        let default_timeout = self.http_timeout.unwrap_or_else(|| {
             let seconds = Environment::u64_or_default("GB_HTTP_CLIENT_TIMEOUT", 10);
             Duration::from_secs(seconds)
         });
-        let gb_gateway = GrowthbookGateway::new(api_url, sdk_key, default_timeout)?;
-        let resp = gb_gateway.get_features(None).await?;
-        let growthbook_writable = Arc::new(RwLock::new(GrowthBook {
-            forced_variations: resp.forced_variations,
-            features: resp.features,
-        }));
-        let gb_rw_clone = Arc::clone(&growthbook_writable);
-
-        tokio::spawn(async move {
-            updated_features_task(gb_gateway, gb_rw_clone, default_interval).await;
+        let source: Arc<dyn FeatureSource> = match self.source {
+            Some(source) => source,
+            None => Arc::new(HttpSource::new(&self.api_url, &self.sdk_key, default_timeout)?),
+        };
+        let cache = self.cache_path.as_deref().map(|path| PayloadCache::new(path, self.cache_max_age));
+        let status = Arc::new(StatusTracker::new(self.staleness));
+        let resp = match source.fetch().await {
+            Ok(resp) => {
+                save_cache(&cache, &resp).await;
+                status.record_success(&resp);
+                resp
+            },
+            Err(e) => match &cache {
+                Some(cache) => match cache.load().await {
+                    Some((resp, saved_at)) => {
+                        warn!("[growthbook-sdk] Failed to fetch features from server, falling back to the payload cache: {:?}", e);
+                        status.record_failure(&e);
+                        status.record_cached(&resp, saved_at);
+                        resp
+                    },
+                    None => return Err(e),
+                },
+                None => return Err(e),
+            },
+        };
+        let mut layers = LayerStack::new(self.layers);
+        layers.refresh().await;
+        let growthbook_writable = Arc::new(RwLock::new(layers.apply(resp)));
+        let notifier = Arc::new(PayloadNotifier::new());
+        let options = Arc::new(self.options);
+        let refresher = Arc::new(Refresher {
+            source,
+            layers: Mutex::new(layers),
+            config: Arc::clone(&growthbook_writable),
+            notifier: Arc::clone(&notifier),
+            cache,
+            status: Arc::clone(&status),
         });
 
-        Ok(GrowthBookClient { gb: growthbook_writable })
+        let watcher = match &self.watch_path {
+            Some(path) => Some(Arc::new(watch_file(Arc::downgrade(&refresher), Arc::clone(&options), path)?)),
+            None => None,
+        };
+        let retry_policy = self.retry_policy;
+        if self.refresh_task {
+            let task_refresher = Arc::clone(&refresher);
+            tokio::spawn(async move {
+                updated_features_task(task_refresher, retry_policy, default_interval).await;
+            });
+            tokio::spawn(staleness_task(Arc::clone(&refresher)));
+        }
+
+        Ok(GrowthBookClient {
+            gb: growthbook_writable,
+            options,
+            notifier,
+            status,
+            refresher,
+            failure_policy: self.failure_policy,
+            _watcher: watcher,
+            handles: Arc::new(()),
+        })
     }
+}
 
-    fn read_gb(&self) -> GrowthBook {
-        match self.gb.read() {
-            Ok(rw_read_guard) => (*rw_read_guard).clone(),
-            Err(e) => {
-                error!("{}", format!("[growthbook-sdk] problem to reading gb mutex data returning empty {:?}", e));
-                GrowthBook {
-                    forced_variations: None,
-                    features: HashMap::new(),
-                }
-            },
+impl GrowthBookClient {
+    pub async fn new(
+        api_url: &str,
+        sdk_key: &str,
+        update_interval: Option<Duration>,
+        http_timeout: Option<Duration>,
+    ) -> Result<Self, GrowthbookError> {
+        let mut builder = GrowthBookClientBuilder::new(api_url, sdk_key);
+        builder.update_interval = update_interval;
+        builder.http_timeout = http_timeout;
+        builder.build().await
+    }
+
+    pub fn builder(
+        api_url: &str,
+        sdk_key: &str,
+    ) -> GrowthBookClientBuilder {
+        GrowthBookClientBuilder::new(api_url, sdk_key)
+    }
+
+    pub fn from_source(source: impl FeatureSource + 'static) -> GrowthBookClientBuilder {
+        GrowthBookClientBuilder::new("", "").source(source)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> GrowthBookClientBuilder {
+        GrowthBookClient::from_source(FileSource::new(&path)).watch_file(path)
+    }
+
+    pub fn is_on_for(
+        &self,
+        feature_name: &str,
+        user_attributes: &GrowthBookAttributes,
+    ) -> bool {
+        self.feature_result_for(feature_name, user_attributes).on
+    }
+
+    pub fn is_off_for(
+        &self,
+        feature_name: &str,
+        user_attributes: &GrowthBookAttributes,
+    ) -> bool {
+        self.feature_result_for(feature_name, user_attributes).off
+    }
+
+    // evaluates against a prebuilt attribute container, so callers can reuse its index across flags
+    pub fn feature_result_for(
+        &self,
+        feature_name: &str,
+        user_attributes: &GrowthBookAttributes,
+    ) -> FeatureResult {
+        self.with_features(|gb| gb.check(feature_name, user_attributes, &self.options))
+            .unwrap_or_else(FeatureResult::fail_closed)
+    }
+
+    pub fn explain(
+        &self,
+        feature_name: &str,
+        user_attributes: Option<Vec<GrowthBookAttribute>>,
+    ) -> FeatureExplanation {
+        let user_attributes = GrowthBookAttributes::new(user_attributes.unwrap_or_default());
+        let gb = self.read_gb();
+        if self.fails_closed() {
+            FeatureExplanation {
+                feature_id: String::from(feature_name),
+                found: gb.features.contains_key(feature_name),
+                rules: vec![],
+                result: FeatureResult::fail_closed(),
+            }
+        } else {
+            gb.explain(feature_name, &user_attributes, &self.options)
+        }
+    }
+
+    // returns whether the features changed
+    pub async fn refresh_now(&self) -> Result<bool, GrowthbookError> {
+        self.refresher.refresh().await
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.status.is_stale()
+    }
+
+    pub fn payload_version(&self) -> Option<DateTime<Utc>> {
+        self.read_gb().date_updated
+    }
+
+    pub fn status(&self) -> ClientStatus {
+        // the stale hook is fired by the refresh task, never from here
+        self.status.status(self.read_gb().features.len())
+    }
+
+    pub fn watch_config<T>(
+        &self,
+        feature_name: &str,
+        user_attributes: Option<Vec<GrowthBookAttribute>>,
+        default: T,
+    ) -> ConfigHandle<T>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        ConfigHandle::spawn(self.clone(), self.notifier.watch_version(), feature_name, user_attributes, default)
+    }
+
+    pub fn watch_feature(
+        &self,
+        feature_name: &str,
+        user_attributes: Option<Vec<GrowthBookAttribute>>,
+    ) -> BoxStream<'static, FeatureResult> {
+        feature_stream(self.clone(), self.notifier.watch_version(), feature_name, user_attributes)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<PayloadUpdate> {
+        self.notifier.subscribe()
+    }
+
+    pub(crate) fn refresher(&self) -> &Arc<Refresher> {
+        &self.refresher
+    }
+
+    // true while a clone is held outside the registry
+    pub(crate) fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.handles) > 1
+    }
+
+    pub(crate) fn report(
+        &self,
+        diagnostic: EvaluationDiagnostic,
+    ) {
+        self.options.report(diagnostic)
+    }
+
+    // None when failing closed, otherwise evaluated against the last-known-good features
+    fn with_features<T>(
+        &self,
+        evaluate: impl FnOnce(&GrowthBook) -> T,
+    ) -> Option<T> {
+        let gb = self.read_gb();
+        if self.fails_closed() {
+            None
+        } else {
+            Some(evaluate(&gb))
         }
     }
+
+    // checked after taking the read guard, which records a poisoned lock
+    fn fails_closed(&self) -> bool {
+        self.failure_policy == FailurePolicy::Closed && !self.status.is_ready()
+    }
+
+    fn read_gb(&self) -> RwLockReadGuard<'_, GrowthBook> {
+        self.gb.read().unwrap_or_else(|e| {
+            warn!("[growthbook-sdk] gb data lock was poisoned, recovering last known good features");
+            self.status.record_poisoned();
+            self.gb.clear_poison();
+            e.into_inner()
+        })
+    }
 }
 
 impl GrowthBookClientTrait for GrowthBookClient {
@@ -112,7 +504,7 @@ impl GrowthBookClientTrait for GrowthBookClient {
         feature_name: &str,
         user_attributes: Option<Vec<GrowthBookAttribute>>,
     ) -> bool {
-        self.read_gb().check(feature_name, &user_attributes).on
+        self.feature_result(feature_name, user_attributes).on
     }
 
     fn is_off(
@@ -120,7 +512,7 @@ impl GrowthBookClientTrait for GrowthBookClient {
         feature_name: &str,
         user_attributes: Option<Vec<GrowthBookAttribute>>,
     ) -> bool {
-        self.read_gb().check(feature_name, &user_attributes).off
+        self.feature_result(feature_name, user_attributes).off
     }
 
     fn feature_result(
@@ -128,11 +520,10 @@ impl GrowthBookClientTrait for GrowthBookClient {
         feature_name: &str,
         user_attributes: Option<Vec<GrowthBookAttribute>>,
     ) -> FeatureResult {
-        self.read_gb().check(feature_name, &user_attributes)
+        self.feature_result_for(feature_name, &GrowthBookAttributes::new(user_attributes.unwrap_or_default()))
     }
 
     fn total_features(&self) -> usize {
-        let gb_data = self.read_gb();
-        gb_data.features.len()
+        self.read_gb().features.len()
     }
 }
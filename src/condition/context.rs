@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::extensions::FindGrowthBookAttribute;
+use crate::model_public::{GrowthBookAttributeValue, GrowthBookAttributes};
+use crate::options::{CustomOperator, EvaluationDiagnostic, EvaluationScope};
+
+pub struct ConditionContext<'a> {
+    user_attributes: &'a GrowthBookAttributes,
+    options: &'a EvaluationScope<'a>,
+}
+
+impl<'a> ConditionContext<'a> {
+    pub fn new(
+        user_attributes: &'a GrowthBookAttributes,
+        options: &'a EvaluationScope<'a>,
+    ) -> Self {
+        ConditionContext { user_attributes, options }
+    }
+
+    pub fn report(
+        &self,
+        diagnostic: EvaluationDiagnostic,
+    ) {
+        self.options.report(diagnostic)
+    }
+
+    // Secure attributes are hashed only for equality and membership operators, the ones whose condition values are hashes
+    pub fn find_hashed_value(
+        &self,
+        attribute_key: &str,
+    ) -> Option<GrowthBookAttributeValue> {
+        let user_value = self.find_value(attribute_key)?;
+        match &self.options.secure_attributes {
+            Some(secure_attributes) if secure_attributes.is_secure(attribute_key) => Some(secure_attributes.hash(&user_value)),
+            _ => Some(user_value),
+        }
+    }
+
+    pub fn custom_operator(
+        &self,
+        name: &str,
+    ) -> Option<&Arc<dyn CustomOperator>> {
+        self.options.custom_operator(name)
+    }
+}
+
+impl FindGrowthBookAttribute for ConditionContext<'_> {
+    fn find_value(
+        &self,
+        attribute_key: &str,
+    ) -> Option<GrowthBookAttributeValue> {
+        self.options.find_attribute(self.user_attributes, attribute_key)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::condition::context::ConditionContext;
 use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
 
 pub struct ElemMatchComparison;
@@ -6,9 +7,9 @@ impl ElemMatchComparison {
     pub fn matches(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &ConditionContext,
         array_size: bool,
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
         match &feature_attribute.value {
             GrowthBookAttributeValue::Object(it) => it.iter().any(|condition_attribute| recursive(parent_attribute, condition_attribute, user_attributes, array_size)),
@@ -1,8 +1,11 @@
+mod context;
 mod elem_match_comparison;
+pub mod model;
 mod operator_condition;
 mod order_comparison;
 mod regex_comparison;
 mod size_comparison;
 mod type_comparison;
-pub mod use_case;
+pub(crate) mod use_case;
+mod value_comparison;
 mod version_comparison;
@@ -0,0 +1,214 @@
+use std::str::FromStr;
+
+use regex::RegexBuilder;
+use serde_json::{Map, Value};
+
+use crate::condition::use_case::{is_operator, ConditionsMatchesAttributes};
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+use crate::explain::model::ConditionExplanation;
+use crate::model_public::{GrowthBookAttribute, GrowthBookAttributes};
+use crate::options::{EvaluationOptions, EvaluationScope};
+
+#[derive(Debug, Clone)]
+pub struct Condition {
+    value: Value,
+    attributes: Vec<GrowthBookAttribute>,
+    options: EvaluationOptions,
+}
+
+impl Condition {
+    pub fn new(value: Value) -> Result<Self, GrowthbookError> {
+        Condition::with_options(value, EvaluationOptions::default())
+    }
+
+    pub fn with_options(
+        value: Value,
+        options: EvaluationOptions,
+    ) -> Result<Self, GrowthbookError> {
+        match &value {
+            Value::Object(map) => validate_condition(map, &options)?,
+            _ => return Err(GrowthbookError::new(GrowthbookErrorCode::InvalidCondition, "Condition must be a JSON object")),
+        }
+        let attributes = GrowthBookAttribute::from(value.clone())?;
+        Ok(Condition { value, attributes, options })
+    }
+
+    pub fn matches(
+        &self,
+        attributes: &GrowthBookAttributes,
+    ) -> bool {
+        self.attributes.matches(attributes, &EvaluationScope::new(&self.options))
+    }
+
+    pub fn explain(
+        &self,
+        attributes: &GrowthBookAttributes,
+    ) -> ConditionExplanation {
+        self.attributes.explain(attributes, &EvaluationScope::new(&self.options))
+    }
+
+    pub fn to_value(&self) -> Value {
+        self.value.clone()
+    }
+}
+
+impl FromStr for Condition {
+    type Err = GrowthbookError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Condition::new(serde_json::from_str(value)?)
+    }
+}
+
+impl TryFrom<Value> for Condition {
+    type Error = GrowthbookError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Condition::new(value)
+    }
+}
+
+fn validate_condition(
+    condition: &Map<String, Value>,
+    options: &EvaluationOptions,
+) -> Result<(), GrowthbookError> {
+    for (key, value) in condition {
+        match key.as_str() {
+            "$or" | "$and" | "$nor" => match value {
+                Value::Array(conditions) => {
+                    for item in conditions {
+                        match item {
+                            Value::Object(it) => validate_condition(it, options)?,
+                            _ => return Err(invalid_condition(&format!("{key} must contain only objects"))),
+                        }
+                    }
+                },
+                _ => return Err(invalid_condition(&format!("{key} must be an array"))),
+            },
+            "$not" => match value {
+                Value::Object(it) => validate_condition(it, options)?,
+                _ => return Err(invalid_condition("$not must be an object")),
+            },
+            _ => validate_value(value, options)?,
+        }
+    }
+    Ok(())
+}
+
+fn validate_value(
+    value: &Value,
+    options: &EvaluationOptions,
+) -> Result<(), GrowthbookError> {
+    match value {
+        Value::Object(map) if !map.is_empty() && map.keys().all(|key| key.starts_with('$')) => {
+            for (operator, argument) in map {
+                validate_operator(operator, argument, options)?;
+            }
+            Ok(())
+        },
+        _ => Ok(()),
+    }
+}
+
+fn validate_operator(
+    operator: &str,
+    argument: &Value,
+    options: &EvaluationOptions,
+) -> Result<(), GrowthbookError> {
+    match operator {
+        "$regex" | "$regexi" => match argument {
+            Value::String(pattern) => match RegexBuilder::new(pattern).case_insensitive(operator == "$regexi").build() {
+                Ok(_) => Ok(()),
+                Err(error) => Err(GrowthbookError::new(GrowthbookErrorCode::InvalidConditionRegex, &format!("Invalid regex for {operator}: {error}"))),
+            },
+            _ => Err(GrowthbookError::new(GrowthbookErrorCode::InvalidConditionRegex, &format!("{operator} must be a string"))),
+        },
+        "$not" | "$size" => validate_value(argument, options),
+        "$elemMatch" => match argument {
+            Value::Object(map) if map.keys().all(|key| key.starts_with('$')) => validate_value(argument, options),
+            Value::Object(map) => validate_condition(map, options),
+            _ => Err(invalid_condition("$elemMatch must be an object")),
+        },
+        it if is_operator(it) || options.custom_operator(it).is_some() => Ok(()),
+        it => Err(GrowthbookError::new(GrowthbookErrorCode::InvalidConditionOperator, &format!("Unknown condition operator: {it}"))),
+    }
+}
+
+fn invalid_condition(message: &str) -> GrowthbookError {
+    GrowthbookError::new(GrowthbookErrorCode::InvalidCondition, message)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use serde_json::{json, Value};
+
+    use crate::condition::model::Condition;
+    use crate::error::GrowthbookErrorCode;
+    use crate::model_public::GrowthBookAttributes;
+
+    // upstream cases the public api rejects up front, the evaluator treats them as never matching
+    const INVALID_CONDITIONS: [&str; 3] = ["unknown operator - pass", "$regex invalid - pass", "$regex invalid - fail"];
+
+    #[tokio::test]
+    async fn evaluate_conditions_with_public_api() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cases = Vec::new();
+        for path in ["./tests/all_cases.json", "./tests/sdk_cases.json"] {
+            let contents: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+            cases.extend(contents["evalCondition"].as_array().expect("Failed to convert to array").clone());
+        }
+
+        for case in &cases {
+            let name = case[0].as_str().expect("Failed to convert to str");
+            let attributes = GrowthBookAttributes::from(case[2].clone())?;
+            match Condition::new(case[1].clone()) {
+                Ok(condition) => {
+                    assert!(!INVALID_CONDITIONS.contains(&name), "EvalCondition accepted: {name}");
+                    assert_eq!(case[3].as_bool(), Some(condition.matches(&attributes)), "EvalCondition failed: {name}");
+                },
+                Err(error) => assert!(INVALID_CONDITIONS.contains(&name), "EvalCondition rejected: {name}: {error:?}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_condition_from_str() -> Result<(), Box<dyn std::error::Error>> {
+        let condition: Condition = r#"{ "country": { "$in": ["BR", "US"] }, "age": { "$gte": 18 } }"#.parse()?;
+
+        assert!(condition.matches(&GrowthBookAttributes::from(json!({ "country": "BR", "age": 21 }))?));
+        assert!(!condition.matches(&GrowthBookAttributes::from(json!({ "country": "AR", "age": 21 }))?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_unknown_operator() {
+        let error = Condition::new(json!({ "name": { "$regx": "hello" } })).expect_err("Expected unknown operator");
+
+        assert!(matches!(error.code, GrowthbookErrorCode::InvalidConditionOperator));
+    }
+
+    #[test]
+    fn reject_unknown_operator_in_nested_condition() {
+        let error = Condition::new(json!({ "$or": [{ "tags": { "$elemMatch": { "$eqq": "a" } } }] })).expect_err("Expected unknown operator");
+
+        assert!(matches!(error.code, GrowthbookErrorCode::InvalidConditionOperator));
+    }
+
+    #[test]
+    fn reject_invalid_regex() {
+        let error = Condition::new(json!({ "name": { "$regex": "(hello" } })).expect_err("Expected invalid regex");
+
+        assert!(matches!(error.code, GrowthbookErrorCode::InvalidConditionRegex));
+    }
+
+    #[test]
+    fn reject_condition_that_is_not_an_object() {
+        let error = Condition::new(json!(["country"])).expect_err("Expected invalid condition");
+
+        assert!(matches!(error.code, GrowthbookErrorCode::InvalidCondition));
+    }
+}
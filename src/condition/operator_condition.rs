@@ -1,3 +1,5 @@
+use crate::condition::context::ConditionContext;
+use crate::condition::value_comparison::ValueComparison;
 use crate::extensions::FindGrowthBookAttribute;
 use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
 
@@ -7,8 +9,8 @@ impl OperatorCondition {
     pub fn not(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
         match &feature_attribute.value {
             GrowthBookAttributeValue::Object(it) => it.iter().all(|next| !recursive(parent_attribute, next, user_attributes, false)),
@@ -19,8 +21,8 @@ impl OperatorCondition {
     pub fn and(
         _parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
         and_nor(&feature_attribute, user_attributes, recursive, false)
     }
@@ -28,8 +30,8 @@ impl OperatorCondition {
     pub fn nor(
         _parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
         and_nor(&feature_attribute, user_attributes, recursive, true)
     }
@@ -37,32 +39,32 @@ impl OperatorCondition {
     pub fn all(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
-        match &feature_attribute.value {
-            GrowthBookAttributeValue::Array(feature_values) => {
-                if let Some(GrowthBookAttributeValue::Array(user_values)) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-                    feature_values.iter().all(|feature_item| user_values.iter().any(|user_item| feature_item == user_item))
-                } else {
-                    false
-                }
-            },
-            _ => false,
-        }
+        all_in_array(parent_attribute, feature_attribute, user_attributes, false)
+    }
+
+    pub fn alli(
+        parent_attribute: Option<&GrowthBookAttribute>,
+        feature_attribute: &GrowthBookAttribute,
+        user_attributes: &ConditionContext,
+        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
+    ) -> bool {
+        all_in_array(parent_attribute, feature_attribute, user_attributes, true)
     }
 
     pub fn ne(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
-        if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
+        if let Some(user_value) = user_attributes.find_hashed_value(&parent_attribute.unwrap_or(feature_attribute).key) {
             !match &user_value {
-                GrowthBookAttributeValue::Array(it) => it.iter().any(|item| item == &feature_attribute.value),
+                GrowthBookAttributeValue::Array(it) => it.iter().any(|item| ValueComparison::strict_eq(&feature_attribute.value, item)),
                 GrowthBookAttributeValue::Empty => true,
-                it => it == &feature_attribute.value,
+                it => ValueComparison::strict_eq(&feature_attribute.value, it),
             }
         } else {
             true
@@ -72,14 +74,14 @@ impl OperatorCondition {
     pub fn eq(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
-        if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
+        if let Some(user_value) = user_attributes.find_hashed_value(&parent_attribute.unwrap_or(feature_attribute).key) {
             match &user_value {
-                GrowthBookAttributeValue::Array(it) => it.iter().any(|item| item == &feature_attribute.value),
+                GrowthBookAttributeValue::Array(it) => it.iter().any(|item| ValueComparison::strict_eq(&feature_attribute.value, item)),
                 GrowthBookAttributeValue::Empty => false,
-                it => it.to_string() == feature_attribute.value.to_string(),
+                it => ValueComparison::strict_eq(&feature_attribute.value, it),
             }
         } else {
             false
@@ -89,8 +91,8 @@ impl OperatorCondition {
     pub fn exists(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
         if let GrowthBookAttributeValue::Bool(it) = feature_attribute.value {
             if user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key).is_some() {
@@ -106,48 +108,44 @@ impl OperatorCondition {
     pub fn is_in(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
-        if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-            match &feature_attribute.value {
-                GrowthBookAttributeValue::Array(feature_array) => feature_array.iter().any(|feature_item| match &user_value {
-                    GrowthBookAttributeValue::Array(user_array) => user_array.iter().any(|user_item| feature_item.to_string() == user_item.to_string()),
-                    GrowthBookAttributeValue::Empty => false,
-                    it => feature_item.to_string() == it.to_string(),
-                }),
-                _ => false,
-            }
-        } else {
-            false
-        }
+        in_array(parent_attribute, feature_attribute, user_attributes, false, false)
+    }
+
+    pub fn ini(
+        parent_attribute: Option<&GrowthBookAttribute>,
+        feature_attribute: &GrowthBookAttribute,
+        user_attributes: &ConditionContext,
+        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
+    ) -> bool {
+        in_array(parent_attribute, feature_attribute, user_attributes, false, true)
     }
 
     pub fn nin(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
-        if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-            match &feature_attribute.value {
-                GrowthBookAttributeValue::Array(feature_array) => feature_array.iter().all(|feature_item| !match &user_value {
-                    GrowthBookAttributeValue::Array(user_array) => user_array.iter().any(|user_item| feature_item.to_string() == user_item.to_string()),
-                    GrowthBookAttributeValue::Empty => false,
-                    it => feature_item.to_string() == it.to_string(),
-                }),
-                _ => false,
-            }
-        } else {
-            false
-        }
+        in_array(parent_attribute, feature_attribute, user_attributes, true, false)
+    }
+
+    pub fn nini(
+        parent_attribute: Option<&GrowthBookAttribute>,
+        feature_attribute: &GrowthBookAttribute,
+        user_attributes: &ConditionContext,
+        _recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
+    ) -> bool {
+        in_array(parent_attribute, feature_attribute, user_attributes, true, true)
     }
 
     pub fn or(
         _parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
         match &feature_attribute.value {
             GrowthBookAttributeValue::Array(it) => {
@@ -168,8 +166,8 @@ impl OperatorCondition {
 
 fn and_nor(
     feature_attribute: &&GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-    recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+    user_attributes: &ConditionContext,
+    recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     negate: bool,
 ) -> bool {
     match &feature_attribute.value {
@@ -187,3 +185,77 @@ fn and_nor(
         _ => false,
     }
 }
+
+fn in_array(
+    parent_attribute: Option<&GrowthBookAttribute>,
+    feature_attribute: &GrowthBookAttribute,
+    user_attributes: &ConditionContext,
+    negate: bool,
+    case_insensitive: bool,
+) -> bool {
+    if let Some(user_value) = find_user_value(parent_attribute, feature_attribute, user_attributes, case_insensitive) {
+        match &feature_attribute.value {
+            GrowthBookAttributeValue::Array(feature_array) => {
+                let mut found = feature_array.iter().map(|feature_item| match &user_value {
+                    GrowthBookAttributeValue::Array(user_array) => user_array.iter().any(|user_item| same_value(feature_item, user_item, case_insensitive)),
+                    GrowthBookAttributeValue::Empty => false,
+                    it => same_value(feature_item, it, case_insensitive),
+                });
+                if negate {
+                    found.all(|it| !it)
+                } else {
+                    found.any(|it| it)
+                }
+            },
+            _ => false,
+        }
+    } else {
+        false
+    }
+}
+
+fn all_in_array(
+    parent_attribute: Option<&GrowthBookAttribute>,
+    feature_attribute: &GrowthBookAttribute,
+    user_attributes: &ConditionContext,
+    case_insensitive: bool,
+) -> bool {
+    match &feature_attribute.value {
+        GrowthBookAttributeValue::Array(feature_values) => {
+            if let Some(GrowthBookAttributeValue::Array(user_values)) = find_user_value(parent_attribute, feature_attribute, user_attributes, case_insensitive) {
+                feature_values
+                    .iter()
+                    .all(|feature_item| user_values.iter().any(|user_item| same_value(feature_item, user_item, case_insensitive)))
+            } else {
+                false
+            }
+        },
+        _ => false,
+    }
+}
+
+fn find_user_value(
+    parent_attribute: Option<&GrowthBookAttribute>,
+    feature_attribute: &GrowthBookAttribute,
+    user_attributes: &ConditionContext,
+    case_insensitive: bool,
+) -> Option<GrowthBookAttributeValue> {
+    let key = &parent_attribute.unwrap_or(feature_attribute).key;
+    if case_insensitive {
+        user_attributes.find_value(key)
+    } else {
+        user_attributes.find_hashed_value(key)
+    }
+}
+
+fn same_value(
+    feature_value: &GrowthBookAttributeValue,
+    user_value: &GrowthBookAttributeValue,
+    case_insensitive: bool,
+) -> bool {
+    if case_insensitive {
+        ValueComparison::strict_eq_case_insensitive(feature_value, user_value)
+    } else {
+        ValueComparison::strict_eq(feature_value, user_value)
+    }
+}
@@ -1,5 +1,6 @@
-use regex::Regex;
+use regex::RegexBuilder;
 
+use crate::condition::context::ConditionContext;
 use crate::extensions::FindGrowthBookAttribute;
 use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
 
@@ -9,23 +10,40 @@ impl RegexComparison {
     pub fn matches(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &ConditionContext,
     ) -> bool {
-        if let GrowthBookAttributeValue::String(feature_value) = &feature_attribute.value {
-            if let Ok(regex) = Regex::new(feature_value) {
-                if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-                    match &user_value {
-                        GrowthBookAttributeValue::Array(it) => it.iter().any(|item| regex.is_match(&item.to_string())),
-                        it => regex.is_match(&it.to_string()),
-                    }
-                } else {
-                    false
+        evaluate(parent_attribute, feature_attribute, user_attributes, false)
+    }
+
+    pub fn matches_case_insensitive(
+        parent_attribute: Option<&GrowthBookAttribute>,
+        feature_attribute: &GrowthBookAttribute,
+        user_attributes: &ConditionContext,
+    ) -> bool {
+        evaluate(parent_attribute, feature_attribute, user_attributes, true)
+    }
+}
+
+fn evaluate(
+    parent_attribute: Option<&GrowthBookAttribute>,
+    feature_attribute: &GrowthBookAttribute,
+    user_attributes: &ConditionContext,
+    case_insensitive: bool,
+) -> bool {
+    if let GrowthBookAttributeValue::String(feature_value) = &feature_attribute.value {
+        if let Ok(regex) = RegexBuilder::new(feature_value).case_insensitive(case_insensitive).build() {
+            if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
+                match &user_value {
+                    GrowthBookAttributeValue::Array(it) => it.iter().any(|item| regex.is_match(&item.to_string())),
+                    it => regex.is_match(&it.to_string()),
                 }
             } else {
                 false
             }
         } else {
-            true
+            false
         }
+    } else {
+        true
     }
 }
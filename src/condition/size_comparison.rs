@@ -1,3 +1,4 @@
+use crate::condition::context::ConditionContext;
 use crate::extensions::FindGrowthBookAttribute;
 use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
 
@@ -7,8 +8,8 @@ impl SizeComparison {
     pub fn matches(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
-        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &[GrowthBookAttribute], bool) -> bool,
+        user_attributes: &ConditionContext,
+        recursive: fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool,
     ) -> bool {
         match &feature_attribute.value {
             GrowthBookAttributeValue::Int(feature_value) => {
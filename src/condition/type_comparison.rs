@@ -1,3 +1,4 @@
+use crate::condition::context::ConditionContext;
 use crate::extensions::FindGrowthBookAttribute;
 use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
 
@@ -7,7 +8,7 @@ impl TypeComparison {
     pub fn matches(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &ConditionContext,
     ) -> bool {
         if let GrowthBookAttributeValue::String(feature_type) = &feature_attribute.value {
             if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
@@ -16,6 +17,7 @@ impl TypeComparison {
                     GrowthBookAttributeValue::Int(_) => feature_type == "number",
                     GrowthBookAttributeValue::Float(_) => feature_type == "number",
                     GrowthBookAttributeValue::Bool(_) => feature_type == "boolean",
+                    GrowthBookAttributeValue::DateTime(_) => feature_type == "string",
                     GrowthBookAttributeValue::Array(_) => feature_type == "array",
                     GrowthBookAttributeValue::Object(it) => {
                         if it.is_empty() {
@@ -1,97 +1,161 @@
+use crate::condition::context::ConditionContext;
 use crate::condition::elem_match_comparison::ElemMatchComparison;
 use crate::condition::operator_condition::OperatorCondition;
 use crate::condition::order_comparison::OrderComparison;
 use crate::condition::regex_comparison::RegexComparison;
 use crate::condition::size_comparison::SizeComparison;
 use crate::condition::type_comparison::TypeComparison;
+use crate::condition::value_comparison::ValueComparison;
 use crate::condition::version_comparison::VersionComparison;
+use crate::explain::model::ConditionExplanation;
 use crate::extensions::FindGrowthBookAttribute;
-use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue, GrowthBookAttributes};
+use crate::options::{EvaluationDiagnostic, EvaluationScope};
+
+type Operator = fn(Option<&GrowthBookAttribute>, &GrowthBookAttribute, &ConditionContext, bool) -> bool;
+
+const OPERATORS: [(&str, Operator); 28] = [
+    ("$not", |parent, feature, context, _| OperatorCondition::not(parent, feature, context, verify)),
+    ("$ne", |parent, feature, context, _| OperatorCondition::ne(parent, feature, context, verify)),
+    ("$and", |parent, feature, context, _| OperatorCondition::and(parent, feature, context, verify)),
+    ("$nor", |parent, feature, context, _| OperatorCondition::nor(parent, feature, context, verify)),
+    ("$or", |parent, feature, context, _| OperatorCondition::or(parent, feature, context, verify)),
+    ("$in", |parent, feature, context, _| OperatorCondition::is_in(parent, feature, context, verify)),
+    ("$ini", |parent, feature, context, _| OperatorCondition::ini(parent, feature, context, verify)),
+    ("$nin", |parent, feature, context, _| OperatorCondition::nin(parent, feature, context, verify)),
+    ("$nini", |parent, feature, context, _| OperatorCondition::nini(parent, feature, context, verify)),
+    ("$gt", |parent, feature, context, array_size| OrderComparison::gt(parent, feature, context, array_size)),
+    ("$gte", |parent, feature, context, array_size| OrderComparison::gte(parent, feature, context, array_size)),
+    ("$lt", |parent, feature, context, array_size| OrderComparison::lt(parent, feature, context, array_size)),
+    ("$lte", |parent, feature, context, array_size| OrderComparison::lte(parent, feature, context, array_size)),
+    ("$eq", |parent, feature, context, _| OperatorCondition::eq(parent, feature, context, verify)),
+    ("$exists", |parent, feature, context, _| OperatorCondition::exists(parent, feature, context, verify)),
+    ("$regex", |parent, feature, context, _| RegexComparison::matches(parent, feature, context)),
+    ("$regexi", |parent, feature, context, _| RegexComparison::matches_case_insensitive(parent, feature, context)),
+    ("$type", |parent, feature, context, _| TypeComparison::matches(parent, feature, context)),
+    ("$size", |parent, feature, context, _| SizeComparison::matches(parent, feature, context, verify)),
+    ("$all", |parent, feature, context, _| OperatorCondition::all(parent, feature, context, verify)),
+    ("$alli", |parent, feature, context, _| OperatorCondition::alli(parent, feature, context, verify)),
+    ("$vgt", |parent, feature, context, _| VersionComparison::vgt(parent, feature, context)),
+    ("$vgte", |parent, feature, context, _| VersionComparison::vgte(parent, feature, context)),
+    ("$vlt", |parent, feature, context, _| VersionComparison::vlt(parent, feature, context)),
+    ("$vlte", |parent, feature, context, _| VersionComparison::vlte(parent, feature, context)),
+    ("$veq", |parent, feature, context, _| VersionComparison::veq(parent, feature, context)),
+    ("$vne", |parent, feature, context, _| VersionComparison::vne(parent, feature, context)),
+    ("$elemMatch", |parent, feature, context, array_size| {
+        ElemMatchComparison::matches(parent, feature, context, array_size, verify)
+    }),
+];
+
+pub fn is_operator(operator: &str) -> bool {
+    find_operator(operator).is_some()
+}
+
+fn find_operator(operator: &str) -> Option<Operator> {
+    OPERATORS.iter().find(|(name, _)| *name == operator).map(|(_, handler)| *handler)
+}
 
 pub trait ConditionsMatchesAttributes {
     fn matches(
         &self,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
     ) -> bool;
+
+    fn explain(
+        &self,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
+    ) -> ConditionExplanation;
 }
 
 impl ConditionsMatchesAttributes for Vec<GrowthBookAttribute> {
     fn matches(
         &self,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
     ) -> bool {
-        self.iter().all(|it| verify(None, it, user_attributes, false))
+        let context = ConditionContext::new(user_attributes, options);
+        self.iter().all(|it| verify(None, it, &context, false))
+    }
+
+    fn explain(
+        &self,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
+    ) -> ConditionExplanation {
+        let context = ConditionContext::new(user_attributes, options);
+        self.iter()
+            .find(|it| !verify(None, it, &context, false))
+            .map(ConditionExplanation::failed)
+            .unwrap_or_else(ConditionExplanation::matched)
     }
 }
 
 fn verify(
     parent_attribute: Option<&GrowthBookAttribute>,
     feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
+    user_attributes: &ConditionContext,
     array_size: bool,
 ) -> bool {
+    if let Some(operator) = find_operator(&feature_attribute.key) {
+        return operator(parent_attribute, feature_attribute, user_attributes, array_size);
+    }
+
     match feature_attribute.key.as_str() {
-        "$not" => OperatorCondition::not(parent_attribute, feature_attribute, user_attributes, verify),
-        "$ne" => OperatorCondition::ne(parent_attribute, feature_attribute, user_attributes, verify),
-        "$and" => OperatorCondition::and(parent_attribute, feature_attribute, user_attributes, verify),
-        "$nor" => OperatorCondition::nor(parent_attribute, feature_attribute, user_attributes, verify),
-        "$or" => OperatorCondition::or(parent_attribute, feature_attribute, user_attributes, verify),
-        "$in" => OperatorCondition::is_in(parent_attribute, feature_attribute, user_attributes, verify),
-        "$nin" => OperatorCondition::nin(parent_attribute, feature_attribute, user_attributes, verify),
-        "$gt" => OrderComparison::gt(parent_attribute, feature_attribute, user_attributes, array_size),
-        "$gte" => OrderComparison::gte(parent_attribute, feature_attribute, user_attributes, array_size),
-        "$lt" => OrderComparison::lt(parent_attribute, feature_attribute, user_attributes, array_size),
-        "$lte" => OrderComparison::lte(parent_attribute, feature_attribute, user_attributes, array_size),
-        "$eq" => OperatorCondition::eq(parent_attribute, feature_attribute, user_attributes, verify),
-        "$exists" => OperatorCondition::exists(parent_attribute, feature_attribute, user_attributes, verify),
-        "$regex" => RegexComparison::matches(parent_attribute, feature_attribute, user_attributes),
-        "$type" => TypeComparison::matches(parent_attribute, feature_attribute, user_attributes),
-        "$size" => SizeComparison::matches(parent_attribute, feature_attribute, user_attributes, verify),
-        "$all" => OperatorCondition::all(parent_attribute, feature_attribute, user_attributes, verify),
-        "$vgt" => VersionComparison::vgt(parent_attribute, feature_attribute, user_attributes),
-        "$vgte" => VersionComparison::vgte(parent_attribute, feature_attribute, user_attributes),
-        "$vlt" => VersionComparison::vlt(parent_attribute, feature_attribute, user_attributes),
-        "$vlte" => VersionComparison::vlte(parent_attribute, feature_attribute, user_attributes),
-        "$veq" => VersionComparison::veq(parent_attribute, feature_attribute, user_attributes),
-        "$vne" => VersionComparison::vne(parent_attribute, feature_attribute, user_attributes),
-        "$elemMatch" => ElemMatchComparison::matches(parent_attribute, feature_attribute, user_attributes, array_size, verify),
+        operator if operator.starts_with('$') && parent_attribute.is_some() => custom_operator(parent_attribute, feature_attribute, user_attributes),
+        // top-level keys name attributes (e.g. `$groups`), so an unknown operator there is one that no attribute answers to
+        operator if operator.starts_with('$') && user_attributes.find_value(operator).is_none() => unknown_operator(None, operator, user_attributes),
         _ => non_operator_or_condition(parent_attribute, feature_attribute, user_attributes),
     }
 }
 
-fn non_operator_or_condition(
+fn custom_operator(
     parent_attribute: Option<&GrowthBookAttribute>,
     feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
+    user_attributes: &ConditionContext,
 ) -> bool {
-    match &feature_attribute.value {
-        GrowthBookAttributeValue::String(_) => string_non_operator(parent_attribute, feature_attribute, user_attributes),
-        GrowthBookAttributeValue::Array(feature_values) => array(&parent_attribute, &feature_attribute, user_attributes, feature_values),
-        GrowthBookAttributeValue::Object(it) => object(parent_attribute, feature_attribute, user_attributes, it),
-        GrowthBookAttributeValue::Empty => empty(&parent_attribute, &feature_attribute, user_attributes),
-        it => fallback(&parent_attribute, feature_attribute, user_attributes, it),
+    match user_attributes.custom_operator(&feature_attribute.key) {
+        Some(operator) => {
+            let user_value = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key);
+            operator.matches(user_value.as_ref(), &feature_attribute.value)
+        },
+        None => unknown_operator(parent_attribute, &feature_attribute.key, user_attributes),
     }
 }
 
-fn string_non_operator(
+fn unknown_operator(
+    parent_attribute: Option<&GrowthBookAttribute>,
+    operator: &str,
+    user_attributes: &ConditionContext,
+) -> bool {
+    user_attributes.report(EvaluationDiagnostic::UnknownOperator {
+        operator: String::from(operator),
+        attribute: parent_attribute.map(|it| it.key.clone()),
+    });
+    false
+}
+
+fn non_operator_or_condition(
     parent_attribute: Option<&GrowthBookAttribute>,
     feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
+    user_attributes: &ConditionContext,
 ) -> bool {
-    if feature_attribute.key.starts_with('$') {
-        false
-    } else {
-        OperatorCondition::eq(parent_attribute, feature_attribute, user_attributes, verify)
+    match &feature_attribute.value {
+        GrowthBookAttributeValue::Array(feature_values) => array(&parent_attribute, &feature_attribute, user_attributes, feature_values),
+        GrowthBookAttributeValue::Object(it) => object(parent_attribute, feature_attribute, user_attributes, it),
+        GrowthBookAttributeValue::Empty => empty(&parent_attribute, &feature_attribute, user_attributes),
+        _ => implicit_eq(parent_attribute, feature_attribute, user_attributes),
     }
 }
 
 fn array(
     parent_attribute: &Option<&GrowthBookAttribute>,
     feature_attribute: &&GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
+    user_attributes: &ConditionContext,
     feature_values: &[GrowthBookAttributeValue],
 ) -> bool {
-    if let Some(GrowthBookAttributeValue::Array(user_values)) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
+    if let Some(GrowthBookAttributeValue::Array(user_values)) = user_attributes.find_hashed_value(&parent_attribute.unwrap_or(feature_attribute).key) {
         if feature_values.len() == user_values.len() {
             feature_values.iter().enumerate().all(|(index, value)| value == &user_values[index])
         } else {
@@ -105,11 +169,15 @@ fn array(
 fn object(
     parent_attribute: Option<&GrowthBookAttribute>,
     feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
+    user_attributes: &ConditionContext,
     it: &[GrowthBookAttribute],
 ) -> bool {
     if it.is_empty() {
         user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key).is_none()
+    } else if it.iter().any(|next| !next.key.starts_with('$')) {
+        user_attributes
+            .find_value(&parent_attribute.unwrap_or(feature_attribute).key)
+            .is_some_and(|user_value| user_value.to_value() == feature_attribute.value.to_value())
     } else {
         it.iter().all(|next| {
             let parent = feature_attribute.aggregate_key(parent_attribute);
@@ -121,7 +189,7 @@ fn object(
 fn empty(
     parent_attribute: &Option<&GrowthBookAttribute>,
     feature_attribute: &&GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
+    user_attributes: &ConditionContext,
 ) -> bool {
     if let Some(it) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
         it == GrowthBookAttributeValue::Empty
@@ -130,16 +198,15 @@ fn empty(
     }
 }
 
-fn fallback(
-    parent_attribute: &Option<&GrowthBookAttribute>,
+fn implicit_eq(
+    parent_attribute: Option<&GrowthBookAttribute>,
     feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
-    it: &GrowthBookAttributeValue,
+    user_attributes: &ConditionContext,
 ) -> bool {
-    if let Some(user_value) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
-        it == &user_value
-    } else {
-        false
+    match user_attributes.find_hashed_value(&parent_attribute.unwrap_or(feature_attribute).key) {
+        Some(GrowthBookAttributeValue::Array(it)) => it.iter().any(|item| ValueComparison::implicit_eq(&feature_attribute.value, item)),
+        Some(user_value) => ValueComparison::implicit_eq(&feature_attribute.value, &user_value),
+        None => false,
     }
 }
 
@@ -156,12 +223,14 @@ impl GrowthBookAttribute {
 #[cfg(test)]
 mod test {
     use std::fs;
+    use std::sync::{Arc, Mutex};
 
     use serde::Deserialize;
-    use serde_json::Value;
+    use serde_json::{json, Value};
 
     use crate::condition::use_case::ConditionsMatchesAttributes;
-    use crate::model_public::GrowthBookAttribute;
+    use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue, GrowthBookAttributes};
+    use crate::options::{EvaluationDiagnostic, EvaluationOptions, EvaluationScope};
 
     #[tokio::test]
     async fn evaluate_conditions() -> Result<(), Box<dyn std::error::Error>> {
@@ -170,8 +239,8 @@ mod test {
         for value in cases.eval_condition {
             let eval_condition = EvalCondition::new(value);
             let vec_condition = &GrowthBookAttribute::from(eval_condition.condition).expect("Failed to create attributes");
-            let vec_attributes = GrowthBookAttribute::from(eval_condition.attribute).expect("Failed to create attributes");
-            let enabled = vec_condition.matches(&vec_attributes);
+            let vec_attributes = GrowthBookAttributes::from(eval_condition.attribute).expect("Failed to create attributes");
+            let enabled = vec_condition.matches(&vec_attributes, &EvaluationScope::new(&EvaluationOptions::default()));
             if enabled != eval_condition.result {
                 panic!("EvalCondition failed: {}", eval_condition.name)
             }
@@ -180,6 +249,82 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn report_unknown_operator() -> Result<(), Box<dyn std::error::Error>> {
+        let reported = Arc::new(Mutex::new(vec![]));
+        let reported_clone = Arc::clone(&reported);
+        let options = EvaluationOptions {
+            diagnostics: Some(Arc::new(move |diagnostic: &EvaluationDiagnostic| {
+                reported_clone.lock().expect("Failed to lock").push(diagnostic.clone())
+            })),
+            ..EvaluationOptions::default()
+        };
+        let vec_condition = GrowthBookAttribute::from(json!({ "name": { "$regx": "hello" } }))?;
+        let vec_attributes = GrowthBookAttributes::from(json!({ "name": "hello" }))?;
+
+        let enabled = vec_condition.matches(&vec_attributes, &EvaluationScope::new(&options));
+
+        assert!(!enabled);
+        assert_eq!(
+            vec![EvaluationDiagnostic::UnknownOperator {
+                operator: String::from("$regx"),
+                attribute: Some(String::from("name")),
+            }],
+            *reported.lock().expect("Failed to lock")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn report_unknown_top_level_operator() -> Result<(), Box<dyn std::error::Error>> {
+        let reported = Arc::new(Mutex::new(vec![]));
+        let reported_clone = Arc::clone(&reported);
+        let options = EvaluationOptions {
+            diagnostics: Some(Arc::new(move |diagnostic: &EvaluationDiagnostic| {
+                reported_clone.lock().expect("Failed to lock").push(diagnostic.clone())
+            })),
+            ..EvaluationOptions::default()
+        };
+        let vec_condition = GrowthBookAttribute::from(json!({ "$xor": [{ "name": "hello" }] }))?;
+        let vec_attributes = GrowthBookAttributes::from(json!({ "name": "hello" }))?;
+
+        let enabled = vec_condition.matches(&vec_attributes, &EvaluationScope::new(&options));
+
+        assert!(!enabled);
+        assert_eq!(
+            vec![EvaluationDiagnostic::UnknownOperator {
+                operator: String::from("$xor"),
+                attribute: None,
+            }],
+            *reported.lock().expect("Failed to lock")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn dispatch_custom_operator() -> Result<(), Box<dyn std::error::Error>> {
+        let mut options = EvaluationOptions::default();
+        options.add_custom_operator(
+            "startsWith",
+            Arc::new(
+                |user_value: Option<&GrowthBookAttributeValue>, condition_value: &GrowthBookAttributeValue| match (user_value, condition_value) {
+                    (Some(GrowthBookAttributeValue::String(user)), GrowthBookAttributeValue::String(prefix)) => user.starts_with(prefix.as_str()),
+                    _ => false,
+                },
+            ),
+        );
+        let vec_condition = GrowthBookAttribute::from(json!({ "name": { "$startsWith": "hel" } }))?;
+
+        assert!(vec_condition.matches(&GrowthBookAttributes::from(json!({ "name": "hello" }))?, &EvaluationScope::new(&options)));
+        assert!(!vec_condition.matches(&GrowthBookAttributes::from(json!({ "name": "world" }))?, &EvaluationScope::new(&options)));
+        assert!(!vec_condition.matches(&GrowthBookAttributes::from(json!({ "other": "hello" }))?, &EvaluationScope::new(&options)));
+        assert!(!vec_condition.matches(&GrowthBookAttributes::from(json!({ "name": "hello" }))?, &EvaluationScope::new(&EvaluationOptions::default())));
+
+        Ok(())
+    }
+
     #[derive(Deserialize, Clone)]
     #[serde(rename_all = "camelCase")]
     struct Cases {
@@ -207,7 +352,14 @@ mod test {
 
     impl Cases {
         pub fn new() -> Self {
-            let contents = fs::read_to_string("./tests/all_cases.json").expect("Should have been able to read the file");
+            let mut cases = Self::read("./tests/all_cases.json");
+            // cases beyond the upstream spec are kept apart from the vendored fixture
+            cases.eval_condition.extend(Self::read("./tests/sdk_cases.json").eval_condition);
+            cases
+        }
+
+        fn read(path: &str) -> Self {
+            let contents = fs::read_to_string(path).expect("Should have been able to read the file");
 
             serde_json::from_str(&contents).expect("Failed to create cases")
         }
@@ -0,0 +1,146 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use crate::model_public::GrowthBookAttributeValue;
+
+pub struct ValueComparison;
+
+impl ValueComparison {
+    pub fn strict_eq(
+        feature_value: &GrowthBookAttributeValue,
+        user_value: &GrowthBookAttributeValue,
+    ) -> bool {
+        match (feature_value, user_value) {
+            (GrowthBookAttributeValue::Int(_) | GrowthBookAttributeValue::Float(_), GrowthBookAttributeValue::Int(_) | GrowthBookAttributeValue::Float(_)) => {
+                feature_value.as_f64() == user_value.as_f64()
+            },
+            (GrowthBookAttributeValue::DateTime(_), _) | (_, GrowthBookAttributeValue::DateTime(_)) => same_instant(feature_value, user_value),
+            (feature, user) => feature == user,
+        }
+    }
+
+    pub fn strict_eq_case_insensitive(
+        feature_value: &GrowthBookAttributeValue,
+        user_value: &GrowthBookAttributeValue,
+    ) -> bool {
+        match (feature_value, user_value) {
+            (GrowthBookAttributeValue::String(feature), GrowthBookAttributeValue::String(user)) => feature.to_lowercase() == user.to_lowercase(),
+            (feature, user) => Self::strict_eq(feature, user),
+        }
+    }
+
+    // follows the JS SDK for conditions without operator: strings compare the stringified
+    // user value, numbers coerce the user value and booleans compare its truthiness
+    pub fn implicit_eq(
+        feature_value: &GrowthBookAttributeValue,
+        user_value: &GrowthBookAttributeValue,
+    ) -> bool {
+        if let GrowthBookAttributeValue::DateTime(_) = user_value {
+            return same_instant(feature_value, user_value);
+        }
+
+        match feature_value {
+            GrowthBookAttributeValue::String(feature) => match user_value {
+                GrowthBookAttributeValue::Empty | GrowthBookAttributeValue::Object(_) => false,
+                user => &user.to_string() == feature,
+            },
+            GrowthBookAttributeValue::Int(_) | GrowthBookAttributeValue::Float(_) => to_number(user_value).is_some_and(|user| feature_value.as_f64() == Some(user)),
+            GrowthBookAttributeValue::Bool(feature) => user_value != &GrowthBookAttributeValue::Empty && &is_truthy(user_value) == feature,
+            feature => feature == user_value,
+        }
+    }
+
+    // two strings compare lexicographically unless both are ISO-8601 instants, anything else is coerced to number (None is NaN);
+    // when one side is a date time, or a number is compared to a non numeric string, both sides are compared as instants
+    pub fn compare(
+        user_value: &GrowthBookAttributeValue,
+        feature_value: &GrowthBookAttributeValue,
+    ) -> Option<Ordering> {
+        match (user_value, feature_value) {
+            (GrowthBookAttributeValue::DateTime(_), _) | (_, GrowthBookAttributeValue::DateTime(_)) => Some(to_instant(user_value)?.cmp(&to_instant(feature_value)?)),
+            (GrowthBookAttributeValue::String(user), GrowthBookAttributeValue::String(feature)) => match (parse_instant(user), parse_instant(feature)) {
+                (Some(user), Some(feature)) => Some(user.cmp(&feature)),
+                _ => Some(user.as_str().cmp(feature.as_str())),
+            },
+            (GrowthBookAttributeValue::Int(_) | GrowthBookAttributeValue::Float(_), GrowthBookAttributeValue::String(it))
+            | (GrowthBookAttributeValue::String(it), GrowthBookAttributeValue::Int(_) | GrowthBookAttributeValue::Float(_))
+                if to_number(&GrowthBookAttributeValue::String(it.clone())).is_none() =>
+            {
+                Some(to_instant(user_value)?.cmp(&to_instant(feature_value)?))
+            },
+            (user, feature) => to_number(user)?.partial_cmp(&to_number(feature)?),
+        }
+    }
+}
+
+fn to_number(value: &GrowthBookAttributeValue) -> Option<f64> {
+    match value {
+        GrowthBookAttributeValue::Int(it) => Some(*it as f64),
+        GrowthBookAttributeValue::Float(it) => Some(*it),
+        GrowthBookAttributeValue::Bool(it) => Some(if *it { 1.0 } else { 0.0 }),
+        GrowthBookAttributeValue::DateTime(it) => Some(it.timestamp_millis() as f64 / 1000.0),
+        GrowthBookAttributeValue::Empty => Some(0.0),
+        GrowthBookAttributeValue::String(it) => {
+            let trimmed = it.trim();
+            if trimmed.is_empty() {
+                Some(0.0)
+            } else {
+                trimmed.parse::<f64>().ok().filter(|number| number.is_finite())
+            }
+        },
+        _ => None,
+    }
+}
+
+fn is_truthy(value: &GrowthBookAttributeValue) -> bool {
+    match value {
+        GrowthBookAttributeValue::Empty => false,
+        GrowthBookAttributeValue::String(it) => !it.is_empty(),
+        GrowthBookAttributeValue::Int(it) => *it != 0,
+        GrowthBookAttributeValue::Float(it) => *it != 0.0 && !it.is_nan(),
+        GrowthBookAttributeValue::Bool(it) => *it,
+        GrowthBookAttributeValue::DateTime(_) => true,
+        GrowthBookAttributeValue::Array(_) | GrowthBookAttributeValue::Object(_) => true,
+    }
+}
+
+fn same_instant(
+    feature_value: &GrowthBookAttributeValue,
+    user_value: &GrowthBookAttributeValue,
+) -> bool {
+    match (to_instant(feature_value), to_instant(user_value)) {
+        (Some(feature), Some(user)) => feature == user,
+        _ => false,
+    }
+}
+
+// ISO-8601 strings (without offset they are read as UTC) and epoch seconds
+// epochs beyond this many seconds (year 5138) can only be milliseconds
+const MAX_EPOCH_SECONDS: f64 = 100_000_000_000.0;
+
+fn to_instant(value: &GrowthBookAttributeValue) -> Option<DateTime<Utc>> {
+    match value {
+        GrowthBookAttributeValue::DateTime(it) => Some(*it),
+        GrowthBookAttributeValue::Int(it) if (*it as f64).abs() >= MAX_EPOCH_SECONDS => DateTime::from_timestamp_millis(*it),
+        GrowthBookAttributeValue::Int(it) => DateTime::from_timestamp(*it, 0),
+        GrowthBookAttributeValue::Float(it) if it.abs() >= MAX_EPOCH_SECONDS => DateTime::from_timestamp_millis(it.round() as i64),
+        GrowthBookAttributeValue::Float(it) => DateTime::from_timestamp_millis((*it * 1000.0).round() as i64),
+        GrowthBookAttributeValue::String(it) => parse_instant(it),
+        _ => None,
+    }
+}
+
+fn parse_instant(value: &str) -> Option<DateTime<Utc>> {
+    let trimmed = value.trim();
+    DateTime::parse_from_rfc3339(trimmed)
+        .map(|date_time| date_time.with_timezone(&Utc))
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%dT%H:%M:%S%.f").ok().map(|date_time| date_time.and_utc()))
+        .or_else(|| {
+            NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date_time| date_time.and_utc())
+        })
+}
@@ -1,5 +1,6 @@
 use regex::Regex;
 
+use crate::condition::context::ConditionContext;
 use crate::extensions::FindGrowthBookAttribute;
 use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
 
@@ -9,7 +10,7 @@ impl VersionComparison {
     pub fn vgt(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &ConditionContext,
     ) -> bool {
         evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.gt(feature_version))
     }
@@ -17,7 +18,7 @@ impl VersionComparison {
     pub fn vgte(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &ConditionContext,
     ) -> bool {
         evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.ge(feature_version))
     }
@@ -25,7 +26,7 @@ impl VersionComparison {
     pub fn vlt(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &ConditionContext,
     ) -> bool {
         evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.lt(feature_version))
     }
@@ -33,7 +34,7 @@ impl VersionComparison {
     pub fn vlte(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &ConditionContext,
     ) -> bool {
         evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.le(feature_version))
     }
@@ -41,7 +42,7 @@ impl VersionComparison {
     pub fn veq(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &ConditionContext,
     ) -> bool {
         evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.eq(feature_version))
     }
@@ -49,7 +50,7 @@ impl VersionComparison {
     pub fn vne(
         parent_attribute: Option<&GrowthBookAttribute>,
         feature_attribute: &GrowthBookAttribute,
-        user_attributes: &[GrowthBookAttribute],
+        user_attributes: &ConditionContext,
     ) -> bool {
         evaluate(parent_attribute, feature_attribute, user_attributes, |feature_version, user_version| user_version.ne(feature_version))
     }
@@ -58,7 +59,7 @@ impl VersionComparison {
 fn evaluate(
     parent_attribute: Option<&GrowthBookAttribute>,
     feature_attribute: &GrowthBookAttribute,
-    user_attributes: &[GrowthBookAttribute],
+    user_attributes: &ConditionContext,
     condition: fn(&str, &str) -> bool,
 ) -> bool {
     if let Some(GrowthBookAttributeValue::String(user_version)) = user_attributes.find_value(&parent_attribute.unwrap_or(feature_attribute).key) {
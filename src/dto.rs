@@ -1,27 +1,29 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::extensions::JsonHelper;
 use crate::model_public::{Experiment, GrowthBookAttribute, GrowthBookAttributeValue};
 use crate::range::model::Range;
 
-#[derive(Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookResponse {
     pub forced_variations: Option<HashMap<String, i64>>,
     pub features: HashMap<String, GrowthBookFeature>,
+    pub date_updated: Option<DateTime<Utc>>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookFeature {
     pub default_value: Option<Value>,
     pub rules: Option<Vec<GrowthBookFeatureRule>>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(untagged)]
 // needs to be in this order
 pub enum GrowthBookFeatureRule {
@@ -32,9 +34,10 @@ pub enum GrowthBookFeatureRule {
     Empty(Value),
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookFeatureRuleForce {
+    pub id: Option<String>,
     pub force: Value,
     pub coverage: Option<f32>,
     range: Option<Vec<f32>>,
@@ -44,13 +47,14 @@ pub struct GrowthBookFeatureRuleForce {
     condition: Option<HashMap<String, Value>>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookFeatureRuleParent {
+    pub id: Option<String>,
     pub parent_conditions: Vec<GrowthBookFeatureRuleParentData>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookFeatureRuleParentData {
     pub id: String,
@@ -58,9 +62,10 @@ pub struct GrowthBookFeatureRuleParentData {
     pub gate: bool,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookFeatureRuleRollout {
+    pub id: Option<String>,
     pub force: Value,
     pub coverage: f32,
     range: Option<Vec<f32>>,
@@ -70,9 +75,10 @@ pub struct GrowthBookFeatureRuleRollout {
     pub hash_version: Option<i64>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GrowthBookFeatureRuleExperiment {
+    pub id: Option<String>,
     pub key: Option<String>,
     pub variations: Vec<Value>,
     name: Option<String>,
@@ -89,6 +95,18 @@ pub struct GrowthBookFeatureRuleExperiment {
     pub condition: Option<Value>,
 }
 
+impl GrowthBookFeatureRule {
+    pub fn id(&self) -> Option<String> {
+        match self {
+            GrowthBookFeatureRule::Experiment(it) => it.id.clone(),
+            GrowthBookFeatureRule::Rollout(it) => it.id.clone(),
+            GrowthBookFeatureRule::Force(it) => it.id.clone(),
+            GrowthBookFeatureRule::Parent(it) => it.id.clone(),
+            GrowthBookFeatureRule::Empty(_) => None,
+        }
+    }
+}
+
 impl GrowthBookFeatureRuleParentData {
     pub fn conditions(&self) -> Option<Vec<GrowthBookAttribute>> {
         option_map_to_attributes(self.condition.clone())
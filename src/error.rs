@@ -2,11 +2,14 @@ use std::env::VarError;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
+use std::time::Duration;
 
-use chrono::OutOfRangeError;
+use chrono::{DateTime, OutOfRangeError, Utc};
+use reqwest::header::RETRY_AFTER;
 use reqwest::Response;
+use serde::Serialize;
 
-#[derive(Debug)]
+#[derive(Serialize, Clone, Debug, PartialEq)]
 pub enum GrowthbookErrorCode {
     GenericError,
     SerdeDeserialize,
@@ -17,12 +20,20 @@ pub enum GrowthbookErrorCode {
     GrowthbookGatewayDeserialize,
     InvalidResponseValueType,
     GrowthBookAttributeIsNotObject,
+    InvalidCondition,
+    InvalidConditionOperator,
+    InvalidConditionRegex,
+    PayloadCache,
+    FeatureSource,
+    FileWatcher,
+    InvalidWebhookSignature,
 }
 
 #[derive(Debug)]
 pub struct GrowthbookError {
     pub code: GrowthbookErrorCode,
     pub message: String,
+    pub retry_after: Option<Duration>,
 }
 
 impl GrowthbookError {
@@ -30,7 +41,19 @@ impl GrowthbookError {
         code: GrowthbookErrorCode,
         message: &str,
     ) -> Self {
-        GrowthbookError { code, message: String::from(message) }
+        GrowthbookError {
+            code,
+            message: String::from(message),
+            retry_after: None,
+        }
+    }
+
+    pub fn with_retry_after(
+        mut self,
+        retry_after: Option<Duration>,
+    ) -> Self {
+        self.retry_after = retry_after;
+        self
     }
 }
 
@@ -54,6 +77,7 @@ impl From<Box<dyn Error>> for GrowthbookError {
         Self {
             code: GrowthbookErrorCode::GenericError,
             message: error.to_string(),
+            retry_after: None,
         }
     }
 }
@@ -63,6 +87,7 @@ impl From<reqwest_middleware::Error> for GrowthbookError {
         Self {
             code: GrowthbookErrorCode::GrowthbookGateway,
             message: error.to_string(),
+            retry_after: None,
         }
     }
 }
@@ -72,6 +97,7 @@ impl From<reqwest::Error> for GrowthbookError {
         Self {
             code: GrowthbookErrorCode::GrowthbookGatewayDeserialize,
             message: error.to_string(),
+            retry_after: None,
         }
     }
 }
@@ -81,6 +107,7 @@ impl From<VarError> for GrowthbookError {
         Self {
             code: GrowthbookErrorCode::MissingEnvironmentVariable,
             message: error.to_string(),
+            retry_after: None,
         }
     }
 }
@@ -90,6 +117,7 @@ impl From<ParseIntError> for GrowthbookError {
         Self {
             code: GrowthbookErrorCode::ParseError,
             message: error.to_string(),
+            retry_after: None,
         }
     }
 }
@@ -99,6 +127,7 @@ impl From<serde_json::Error> for GrowthbookError {
         Self {
             code: GrowthbookErrorCode::ParseError,
             message: error.to_string(),
+            retry_after: None,
         }
     }
 }
@@ -108,6 +137,7 @@ impl From<OutOfRangeError> for GrowthbookError {
         Self {
             code: GrowthbookErrorCode::DurationOutOfRangeError,
             message: error.to_string(),
+            retry_after: None,
         }
     }
 }
@@ -117,6 +147,15 @@ impl From<Response> for GrowthbookError {
         Self {
             code: GrowthbookErrorCode::GrowthbookGateway,
             message: format!("Failed to get features. StatusCode={}", response.status()),
+            retry_after: retry_after(&response),
         }
     }
 }
+
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc).signed_duration_since(Utc::now()).to_std().ok(),
+    }
+}
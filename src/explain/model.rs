@@ -0,0 +1,193 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::condition::use_case::ConditionsMatchesAttributes;
+use crate::model_public::{FeatureResult, GrowthBookAttribute, GrowthBookAttributes};
+use crate::options::EvaluationScope;
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureExplanation {
+    pub feature_id: String,
+    pub found: bool,
+    pub rules: Vec<RuleExplanation>,
+    pub result: FeatureResult,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleExplanation {
+    pub id: Option<String>,
+    pub index: usize,
+    pub kind: RuleKind,
+    pub condition: Option<ConditionExplanation>,
+    pub filtered_out: Option<bool>,
+    pub in_namespace: Option<bool>,
+    pub hash_attribute: Option<String>,
+    pub hash_value: Option<Value>,
+    pub bucket: Option<f32>,
+    pub in_coverage: Option<bool>,
+    pub variation_id: Option<i64>,
+    pub prerequisites: Vec<PrerequisiteExplanation>,
+    pub decision: RuleDecision,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleKind {
+    Force,
+    Rollout,
+    Experiment,
+    Parent,
+    Empty,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleDecision {
+    Applied,
+    ConditionNotMet,
+    FilteredOut,
+    NotInNamespace,
+    MissingHashAttribute,
+    NotInCoverage,
+    NotInExperiment,
+    PrerequisitesMet,
+    PrerequisiteNotMet,
+    CyclicPrerequisite,
+    Skipped,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionExplanation {
+    pub matched: bool,
+    pub failed_condition: Option<Value>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrerequisiteExplanation {
+    pub id: String,
+    pub value: Value,
+    pub met: bool,
+}
+
+impl RuleExplanation {
+    pub fn new(
+        index: usize,
+        kind: RuleKind,
+    ) -> Self {
+        RuleExplanation {
+            id: None,
+            index,
+            kind,
+            condition: None,
+            filtered_out: None,
+            in_namespace: None,
+            hash_attribute: None,
+            hash_value: None,
+            bucket: None,
+            in_coverage: None,
+            variation_id: None,
+            prerequisites: vec![],
+            decision: RuleDecision::Skipped,
+        }
+    }
+}
+
+// Records why a rule matched or not while it is being evaluated; disabled traces skip all bookkeeping
+pub(crate) struct RuleTrace(Option<RuleExplanation>);
+
+impl RuleTrace {
+    pub fn new(
+        enabled: bool,
+        index: usize,
+        kind: RuleKind,
+    ) -> Self {
+        RuleTrace(enabled.then(|| RuleExplanation::new(index, kind)))
+    }
+
+    pub fn disabled() -> Self {
+        RuleTrace(None)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn record(
+        &mut self,
+        f: impl FnOnce(&mut RuleExplanation),
+    ) {
+        if let Some(explanation) = self.0.as_mut() {
+            f(explanation)
+        }
+    }
+
+    pub fn check_conditions(
+        &mut self,
+        conditions: &Vec<GrowthBookAttribute>,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
+    ) -> bool {
+        match self.0.as_mut() {
+            Some(explanation) => {
+                let condition = conditions.explain(user_attributes, options);
+                let matched = condition.matched;
+                explanation.condition = Some(condition);
+                matched
+            },
+            None => conditions.matches(user_attributes, options),
+        }
+    }
+
+    pub fn decide(
+        &mut self,
+        decision: RuleDecision,
+    ) {
+        self.record(|it| it.decision = decision)
+    }
+
+    pub fn skip(
+        &mut self,
+        decision: RuleDecision,
+    ) -> Option<FeatureResult> {
+        self.decide(decision);
+        None
+    }
+
+    pub fn conclude(
+        &mut self,
+        result: Option<FeatureResult>,
+        otherwise: RuleDecision,
+    ) -> Option<FeatureResult> {
+        self.decide(if result.is_some() { RuleDecision::Applied } else { otherwise });
+        result
+    }
+
+    pub fn finish(
+        self,
+        id: Option<String>,
+    ) -> Option<RuleExplanation> {
+        self.0.map(|it| RuleExplanation { id, ..it })
+    }
+}
+
+impl ConditionExplanation {
+    pub fn matched() -> Self {
+        ConditionExplanation {
+            matched: true,
+            failed_condition: None,
+        }
+    }
+
+    pub fn failed(condition: &GrowthBookAttribute) -> Self {
+        let mut map = Map::new();
+        map.insert(condition.key.clone(), condition.value.to_value());
+        ConditionExplanation {
+            matched: false,
+            failed_condition: Some(Value::Object(map)),
+        }
+    }
+}
@@ -3,103 +3,107 @@ use std::collections::HashMap;
 use serde_json::Value;
 
 use crate::dto::GrowthBookFeatureRuleExperiment;
-use crate::extensions::{FindGrowthBookAttribute, JsonHelper};
+use crate::explain::model::{RuleDecision, RuleTrace};
+use crate::extensions::JsonHelper;
 use crate::hash::{HashCode, HashCodeVersion};
-use crate::model_public::{ExperimentResult, FeatureResult, GrowthBookAttribute};
+use crate::model_public::{ExperimentResult, FeatureResult, GrowthBookAttributeValue, GrowthBookAttributes};
 use crate::namespace::use_case::Namespace;
+use crate::options::EvaluationScope;
 use crate::range::model::Range;
 
 impl GrowthBookFeatureRuleExperiment {
     pub fn get_match_value(
         &self,
         feature_name: &str,
-        user_attributes: &Vec<GrowthBookAttribute>,
+        user_attributes: &GrowthBookAttributes,
         forced_variations: &Option<HashMap<String, i64>>,
+        options: &EvaluationScope<'_>,
     ) -> Option<FeatureResult> {
-        if let Some(feature_attribute) = &self.hash_attribute {
-            self.check_experiment(&feature_name, user_attributes, forced_variations, feature_attribute)
-        } else {
-            let fallback_attribute = self.get_fallback_attribute();
-            self.check_experiment(&feature_name, user_attributes, forced_variations, &fallback_attribute)
-        }
+        self.evaluate(feature_name, user_attributes, forced_variations, options, &mut RuleTrace::disabled())
     }
 
-    fn check_experiment(
+    pub(crate) fn evaluate(
         &self,
-        feature_name: &&str,
-        user_attributes: &Vec<GrowthBookAttribute>,
+        feature_name: &str,
+        user_attributes: &GrowthBookAttributes,
         forced_variations: &Option<HashMap<String, i64>>,
-        feature_attribute: &str,
+        options: &EvaluationScope<'_>,
+        trace: &mut RuleTrace,
     ) -> Option<FeatureResult> {
-        if let Some(user_value) = user_attributes.find_value(feature_attribute) {
-            if let Some((namespace, range)) = &self.namespace_range() {
-                if !Namespace::is_in(&user_value, namespace, range) {
-                    return None;
-                }
+        let feature_attribute = self.hash_attribute.clone().unwrap_or(self.get_fallback_attribute());
+        trace.record(|it| it.hash_attribute = Some(feature_attribute.clone()));
+        let Some(user_value) = options.find_attribute(user_attributes, &feature_attribute) else {
+            return trace.skip(RuleDecision::MissingHashAttribute);
+        };
+        trace.record(|it| it.hash_value = Some(user_value.to_value()));
+
+        if let Some((namespace, range)) = &self.namespace_range() {
+            let in_namespace = Namespace::is_in(&user_value, namespace, range);
+            trace.record(|it| it.in_namespace = Some(in_namespace));
+            if !in_namespace {
+                return trace.skip(RuleDecision::NotInNamespace);
             }
+        }
 
-            if let Some(forced_variation) = self.forced_variation(feature_name, user_attributes, forced_variations) {
-                return Some(forced_variation);
-            }
+        let bucket = HashCode::hash_code(&user_value.to_string(), &self.seed(feature_name), HashCodeVersion::from(self.hash_version));
+        let user_weight = bucket.unwrap_or(-1.0);
+        let index = choose_variation(user_weight, self.ranges());
+        trace.record(|it| {
+            it.bucket = bucket;
+            it.in_coverage = Some(index >= 0);
+            it.variation_id = (index >= 0).then_some(index);
+        });
+
+        if let Some(forced_variation) = self.forced_variation(feature_name, &user_value, forced_variations) {
+            trace.record(|it| it.variation_id = forced_variation.experiment_result.as_ref().map(|result| result.variation_id));
+            return trace.conclude(Some(forced_variation), RuleDecision::NotInExperiment);
+        }
 
-            let user_weight = HashCode::hash_code(&user_value.to_string(), &self.seed(feature_name), HashCodeVersion::from(self.hash_version)).unwrap_or(-1.0);
-            let ranges = self.ranges();
-            let index = choose_variation(user_weight, ranges);
-            if index >= 0 {
-                let usize_index = index as usize;
-                let value = self.variations[usize_index].clone();
-                let (meta_value, pass_through) = self.get_meta_value(usize_index);
-                if !pass_through {
-                    return Some(FeatureResult::experiment(
-                        value.clone(),
-                        self.model_experiment(),
-                        create_experiment_result(
-                            feature_name,
-                            value.clone(),
-                            index,
-                            true,
-                            Some(feature_attribute.to_string()),
-                            Some(user_value.to_value()),
-                            Some(user_weight),
-                            meta_value,
-                        ),
-                    ));
-                }
-            }
+        if index < 0 {
+            return trace.skip(RuleDecision::NotInCoverage);
         }
 
-        None
+        let usize_index = index as usize;
+        let value = self.variations[usize_index].clone();
+        let (meta_value, pass_through) = self.get_meta_value(usize_index);
+        if pass_through {
+            return trace.skip(RuleDecision::NotInExperiment);
+        }
+
+        let result = FeatureResult::experiment(
+            value.clone(),
+            self.model_experiment(),
+            create_experiment_result(feature_name, value, index, true, Some(feature_attribute), Some(user_value.to_value()), Some(user_weight), meta_value),
+        );
+        trace.conclude(Some(result), RuleDecision::NotInExperiment)
     }
 
     fn forced_variation(
         &self,
         feature_name: &str,
-        user_attributes: &Vec<GrowthBookAttribute>,
+        user_value: &GrowthBookAttributeValue,
         forced_variations: &Option<HashMap<String, i64>>,
     ) -> Option<FeatureResult> {
         if let Some(forced_variations) = forced_variations {
             if let Some(found_forced_variation) = forced_variations.get(feature_name) {
-                let hash_attribute = self.hash_attribute.clone().unwrap_or(self.get_fallback_attribute());
-                if let Some(user_value) = user_attributes.find_value(&hash_attribute) {
-                    let forced_variation_index = *found_forced_variation as usize;
-                    let value = self.variations[forced_variation_index].clone();
-                    let (meta_value, pass_through) = self.get_meta_value(forced_variation_index);
-                    if !pass_through {
-                        return Some(FeatureResult::experiment(
+                let forced_variation_index = *found_forced_variation as usize;
+                let value = self.variations[forced_variation_index].clone();
+                let (meta_value, pass_through) = self.get_meta_value(forced_variation_index);
+                if !pass_through {
+                    return Some(FeatureResult::experiment(
+                        value.clone(),
+                        self.model_experiment(),
+                        create_experiment_result(
+                            feature_name,
                             value.clone(),
-                            self.model_experiment(),
-                            create_experiment_result(
-                                feature_name,
-                                value.clone(),
-                                *found_forced_variation,
-                                true,
-                                self.hash_attribute.clone(),
-                                Some(user_value.to_value()),
-                                None,
-                                meta_value,
-                            ),
-                        ));
-                    }
+                            *found_forced_variation,
+                            true,
+                            self.hash_attribute.clone(),
+                            Some(user_value.to_value()),
+                            None,
+                            meta_value,
+                        ),
+                    ));
                 }
             }
         }
@@ -159,6 +163,8 @@ fn create_experiment_result(
         bucket,
         key,
         sticky_bucket_used: false,
+        rule_id: None,
+        rule_index: None,
     }
 }
 
@@ -1,49 +1,61 @@
-use crate::condition::use_case::ConditionsMatchesAttributes;
 use crate::coverage::model::Coverage;
 use crate::dto::GrowthBookFeatureRuleForce;
-use crate::extensions::FindGrowthBookAttribute;
+use crate::explain::model::{RuleDecision, RuleTrace};
 use crate::filter::use_case::Filter;
-use crate::model_public::{FeatureResult, GrowthBookAttribute};
+use crate::model_public::{FeatureResult, GrowthBookAttributes};
+use crate::options::EvaluationScope;
 
 impl GrowthBookFeatureRuleForce {
     pub fn get_match_value(
         &self,
         feature_name: &str,
-        user_attributes: &Vec<GrowthBookAttribute>,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
+    ) -> Option<FeatureResult> {
+        self.evaluate(feature_name, user_attributes, options, &mut RuleTrace::disabled())
+    }
+
+    pub(crate) fn evaluate(
+        &self,
+        feature_name: &str,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
+        trace: &mut RuleTrace,
     ) -> Option<FeatureResult> {
         if let Some(filters) = &self.filters {
-            let hash_attribute = self.get_fallback_attribute();
-            if Filter::is_filtered_out(filters, &hash_attribute, user_attributes) {
-                return None;
+            let filtered_out = Filter::is_filtered_out(filters, &self.get_fallback_attribute(), user_attributes, options);
+            trace.record(|it| it.filtered_out = Some(filtered_out));
+            if filtered_out {
+                return trace.skip(RuleDecision::FilteredOut);
             }
         }
 
         if let Some(feature_attributes) = self.conditions() {
-            if feature_attributes.matches(user_attributes) {
-                self.check_range_or_force(feature_name, user_attributes)
-            } else {
-                None
+            if !trace.check_conditions(&feature_attributes, user_attributes, options) {
+                return trace.skip(RuleDecision::ConditionNotMet);
             }
-        } else {
-            self.check_range_or_force(feature_name, user_attributes)
         }
-    }
 
-    fn check_range_or_force(
-        &self,
-        feature_name: &str,
-        user_attributes: &Vec<GrowthBookAttribute>,
-    ) -> Option<FeatureResult> {
-        if let Some(range) = self.range() {
-            let fallback_attribute = self.get_fallback_attribute();
-            if let Some(user_value) = user_attributes.find_value(&fallback_attribute) {
-                let seed = self.seed.clone().unwrap_or(feature_name.to_string());
-                Coverage::check(&user_value, None, Some(range), &seed, self.hash_version, self.force.clone())
-            } else {
-                None
-            }
-        } else {
-            Some(FeatureResult::force(self.force.clone()))
-        }
+        let Some(range) = self.range() else {
+            return trace.conclude(Some(FeatureResult::force(self.force.clone())), RuleDecision::NotInCoverage);
+        };
+
+        let fallback_attribute = self.get_fallback_attribute();
+        trace.record(|it| it.hash_attribute = Some(fallback_attribute.clone()));
+        let Some(user_value) = options.find_attribute(user_attributes, &fallback_attribute) else {
+            return trace.skip(RuleDecision::MissingHashAttribute);
+        };
+
+        let seed = self.seed.clone().unwrap_or(feature_name.to_string());
+        let bucket = Coverage::bucket(&user_value, &seed, self.hash_version);
+        let in_coverage = bucket.map(|it| Coverage::contains(it, None, Some(range)));
+        trace.record(|it| {
+            it.hash_value = Some(user_value.to_value());
+            it.bucket = bucket;
+            it.in_coverage = in_coverage;
+        });
+
+        let result = in_coverage.unwrap_or(false).then(|| FeatureResult::force(self.force.clone()));
+        trace.conclude(result, RuleDecision::NotInCoverage)
     }
 }
@@ -1,14 +1,19 @@
 use crate::condition::use_case::ConditionsMatchesAttributes;
 use crate::dto::GrowthBookFeatureRuleParentData;
-use crate::model_public::{FeatureResult, GrowthBookAttribute, GrowthBookAttributeValue};
+use crate::model_public::{FeatureResult, GrowthBookAttribute, GrowthBookAttributeValue, GrowthBookAttributes};
+use crate::options::EvaluationScope;
 
 impl GrowthBookFeatureRuleParentData {
     pub fn is_met(
         &self,
         feature: FeatureResult,
+        options: &EvaluationScope<'_>,
     ) -> bool {
         if let Some(feature_attributes) = self.conditions() {
-            feature_attributes.matches(&[GrowthBookAttribute::new(String::from("value"), GrowthBookAttributeValue::from(feature.value))])
+            feature_attributes.matches(
+                &GrowthBookAttributes::new(vec![GrowthBookAttribute::new(String::from("value"), GrowthBookAttributeValue::from(feature.value))]),
+                options,
+            )
         } else {
             true
         }
@@ -1,42 +1,56 @@
-use crate::condition::use_case::ConditionsMatchesAttributes;
 use crate::coverage::model::Coverage;
 use crate::dto::GrowthBookFeatureRuleRollout;
-use crate::extensions::FindGrowthBookAttribute;
-use crate::model_public::{FeatureResult, GrowthBookAttribute};
+use crate::explain::model::{RuleDecision, RuleTrace};
+use crate::model_public::{FeatureResult, GrowthBookAttributes};
+use crate::options::EvaluationScope;
 
 impl GrowthBookFeatureRuleRollout {
     pub fn get_match_value(
         &self,
         feature_name: &str,
-        user_attributes: &Vec<GrowthBookAttribute>,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
     ) -> Option<FeatureResult> {
-        if let Some(feature_attributes) = &self.conditions() {
-            if feature_attributes.matches(user_attributes) {
-                self.check_coverage(feature_name, user_attributes)
-            } else {
-                None
-            }
-        } else {
-            self.check_coverage(feature_name, user_attributes)
-        }
+        self.evaluate(feature_name, user_attributes, options, &mut RuleTrace::disabled())
     }
 
-    fn check_coverage(
+    pub(crate) fn evaluate(
         &self,
         feature_name: &str,
-        user_attributes: &Vec<GrowthBookAttribute>,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
+        trace: &mut RuleTrace,
     ) -> Option<FeatureResult> {
-        if let Some(hash_attribute) = &self.hash_attribute {
-            if let Some(user_value) = user_attributes.find_value(hash_attribute) {
-                return Coverage::check(&user_value, Some(self.coverage), self.range(), feature_name, self.hash_version, self.force.clone());
+        if let Some(feature_attributes) = self.conditions() {
+            if !trace.check_conditions(&feature_attributes, user_attributes, options) {
+                return trace.skip(RuleDecision::ConditionNotMet);
             }
         }
 
-        let fallback_attribute = self.get_fallback_attribute();
-        if let Some(user_value) = user_attributes.find_value(&fallback_attribute) {
-            return Coverage::check(&user_value, Some(self.coverage), self.range(), feature_name, self.hash_version, self.force.clone());
-        }
+        let hashed = self
+            .hash_attribute
+            .as_ref()
+            .and_then(|hash_attribute| options.find_attribute(user_attributes, hash_attribute).map(|user_value| (hash_attribute.clone(), user_value)))
+            .or_else(|| {
+                let fallback_attribute = self.get_fallback_attribute();
+                options.find_attribute(user_attributes, &fallback_attribute).map(|user_value| (fallback_attribute, user_value))
+            });
+
+        let Some((hash_attribute, user_value)) = hashed else {
+            trace.record(|it| it.hash_attribute = Some(self.hash_attribute.clone().unwrap_or(self.get_fallback_attribute())));
+            return trace.skip(RuleDecision::MissingHashAttribute);
+        };
+
+        let bucket = Coverage::bucket(&user_value, feature_name, self.hash_version);
+        let in_coverage = bucket.map(|it| Coverage::contains(it, Some(self.coverage), self.range()));
+        trace.record(|it| {
+            it.hash_attribute = Some(hash_attribute);
+            it.hash_value = Some(user_value.to_value());
+            it.bucket = bucket;
+            it.in_coverage = in_coverage;
+        });
 
-        None
+        let result = in_coverage.unwrap_or(false).then(|| FeatureResult::force(self.force.clone()));
+        trace.conclude(result, RuleDecision::NotInCoverage)
     }
 }
@@ -1,63 +1,63 @@
 use std::collections::HashMap;
 
-use crate::dto::{GrowthBookFeature, GrowthBookFeatureRule};
-use crate::model_public::{FeatureResult, GrowthBookAttribute};
+use crate::dto::{GrowthBookFeature, GrowthBookFeatureRule, GrowthBookFeatureRuleParent};
+use crate::explain::model::{PrerequisiteExplanation, RuleDecision, RuleExplanation, RuleKind, RuleTrace};
+use crate::model_public::{FeatureResult, GrowthBookAttributes};
+use crate::options::EvaluationScope;
 
 impl GrowthBookFeature {
     pub fn get_value(
         &self,
         feature_name: &str,
         feature_name_decorate: Vec<String>,
-        user_attributes: &Vec<GrowthBookAttribute>,
+        user_attributes: &GrowthBookAttributes,
         forced_variations: &Option<HashMap<String, i64>>,
-        all_features: HashMap<String, GrowthBookFeature>,
+        all_features: &HashMap<String, GrowthBookFeature>,
+        options: &EvaluationScope<'_>,
     ) -> FeatureResult {
-        if let Some(rules) = &self.rules {
-            for rule in rules {
-                match rule {
-                    GrowthBookFeatureRule::Force(it) => {
-                        if let Some(feature) = it.get_match_value(feature_name, user_attributes) {
-                            return feature;
-                        }
-                    },
-                    GrowthBookFeatureRule::Rollout(it) => {
-                        if let Some(feature) = it.get_match_value(feature_name, user_attributes) {
-                            return feature;
-                        }
-                    },
-                    GrowthBookFeatureRule::Experiment(it) => {
-                        if let Some(feature) = it.get_match_value(feature_name, user_attributes, forced_variations) {
-                            return feature;
-                        }
-                    },
-                    GrowthBookFeatureRule::Parent(it) => {
-                        for parent in &it.parent_conditions {
-                            let parent_feature_name = &parent.id;
-                            if feature_name_decorate.contains(parent_feature_name) {
-                                return FeatureResult::cyclic_prerequisite();
-                            }
-
-                            let mut updated_decorate = feature_name_decorate.clone();
-                            updated_decorate.push(String::from(feature_name));
+        self.evaluate(feature_name, &feature_name_decorate, user_attributes, forced_variations, all_features, options, None)
+    }
 
-                            let parent_response = if let Some(parent_feature) = all_features.get(parent_feature_name) {
-                                parent_feature.get_value(parent_feature_name, updated_decorate, user_attributes, forced_variations, all_features.clone())
-                            } else {
-                                FeatureResult::unknown_feature()
-                            };
+    pub fn explain(
+        &self,
+        feature_name: &str,
+        user_attributes: &GrowthBookAttributes,
+        forced_variations: &Option<HashMap<String, i64>>,
+        all_features: &HashMap<String, GrowthBookFeature>,
+        options: &EvaluationScope<'_>,
+    ) -> (Vec<RuleExplanation>, FeatureResult) {
+        let mut explanations = vec![];
+        let result = self.evaluate(feature_name, &[], user_attributes, forced_variations, all_features, options, Some(&mut explanations));
+        (explanations, result)
+    }
 
-                            if parent_response.source == "cyclicPrerequisite" {
-                                return FeatureResult::cyclic_prerequisite();
-                            }
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate(
+        &self,
+        feature_name: &str,
+        feature_name_decorate: &[String],
+        user_attributes: &GrowthBookAttributes,
+        forced_variations: &Option<HashMap<String, i64>>,
+        all_features: &HashMap<String, GrowthBookFeature>,
+        options: &EvaluationScope<'_>,
+        mut explanations: Option<&mut Vec<RuleExplanation>>,
+    ) -> FeatureResult {
+        if let Some(rules) = &self.rules {
+            for (index, rule) in rules.iter().enumerate() {
+                let mut trace = RuleTrace::new(explanations.is_some(), index, rule_kind(rule));
+                let result = match rule {
+                    GrowthBookFeatureRule::Force(it) => it.evaluate(feature_name, user_attributes, options, &mut trace),
+                    GrowthBookFeatureRule::Rollout(it) => it.evaluate(feature_name, user_attributes, options, &mut trace),
+                    GrowthBookFeatureRule::Experiment(it) => it.evaluate(feature_name, user_attributes, forced_variations, options, &mut trace),
+                    GrowthBookFeatureRule::Parent(it) => check_prerequisites(it, feature_name, feature_name_decorate, user_attributes, forced_variations, all_features, options, &mut trace),
+                    GrowthBookFeatureRule::Empty(_) => None,
+                };
 
-                            if !parent.is_met(parent_response) {
-                                return FeatureResult::prerequisite();
-                            }
-                        }
-                    },
-                    GrowthBookFeatureRule::Empty(_) => {
-                        continue;
-                    },
+                if let (Some(explanations), Some(explanation)) = (explanations.as_deref_mut(), trace.finish(rule.id())) {
+                    explanations.push(explanation);
+                }
+                if let Some(feature) = result {
+                    return feature.with_rule(rule.id(), index);
                 }
             }
         }
@@ -65,3 +65,66 @@ impl GrowthBookFeature {
         FeatureResult::from_default_value(self.default_value.clone())
     }
 }
+
+#[allow(clippy::too_many_arguments)]
+fn check_prerequisites(
+    rule: &GrowthBookFeatureRuleParent,
+    feature_name: &str,
+    feature_name_decorate: &[String],
+    user_attributes: &GrowthBookAttributes,
+    forced_variations: &Option<HashMap<String, i64>>,
+    all_features: &HashMap<String, GrowthBookFeature>,
+    options: &EvaluationScope<'_>,
+    trace: &mut RuleTrace,
+) -> Option<FeatureResult> {
+    trace.decide(RuleDecision::PrerequisitesMet);
+    for parent in &rule.parent_conditions {
+        let parent_feature_name = &parent.id;
+        if feature_name_decorate.contains(parent_feature_name) {
+            trace.decide(RuleDecision::CyclicPrerequisite);
+            return Some(FeatureResult::cyclic_prerequisite());
+        }
+
+        let mut updated_decorate = feature_name_decorate.to_vec();
+        updated_decorate.push(String::from(feature_name));
+
+        let parent_response = if let Some(parent_feature) = all_features.get(parent_feature_name) {
+            parent_feature.evaluate(parent_feature_name, &updated_decorate, user_attributes, forced_variations, all_features, options, None)
+        } else {
+            FeatureResult::unknown_feature()
+        };
+
+        if parent_response.source == "cyclicPrerequisite" {
+            trace.decide(RuleDecision::CyclicPrerequisite);
+            return Some(FeatureResult::cyclic_prerequisite());
+        }
+
+        let value = trace.is_enabled().then(|| parent_response.value.clone());
+        let met = parent.is_met(parent_response, options);
+        if let Some(value) = value {
+            trace.record(|it| {
+                it.prerequisites.push(PrerequisiteExplanation {
+                    id: parent_feature_name.clone(),
+                    value,
+                    met,
+                })
+            });
+        }
+        if !met {
+            trace.decide(RuleDecision::PrerequisiteNotMet);
+            return Some(FeatureResult::prerequisite());
+        }
+    }
+
+    None
+}
+
+fn rule_kind(rule: &GrowthBookFeatureRule) -> RuleKind {
+    match rule {
+        GrowthBookFeatureRule::Force(_) => RuleKind::Force,
+        GrowthBookFeatureRule::Rollout(_) => RuleKind::Rollout,
+        GrowthBookFeatureRule::Experiment(_) => RuleKind::Experiment,
+        GrowthBookFeatureRule::Parent(_) => RuleKind::Parent,
+        GrowthBookFeatureRule::Empty(_) => RuleKind::Empty,
+    }
+}
@@ -1,8 +1,9 @@
 use serde_json::Value;
 
-use crate::extensions::{FindGrowthBookAttribute, JsonHelper};
+use crate::extensions::JsonHelper;
 use crate::hash::{HashCode, HashCodeVersion};
-use crate::model_public::GrowthBookAttribute;
+use crate::model_public::GrowthBookAttributes;
+use crate::options::EvaluationScope;
 use crate::range::model::Range;
 
 pub struct Filter;
@@ -11,10 +12,11 @@ impl Filter {
     pub fn is_filtered_out(
         filters: &Value,
         hash_attribute: &str,
-        user_attributes: &Vec<GrowthBookAttribute>,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationScope<'_>,
     ) -> bool {
         for filter in filters.force_array(vec![]) {
-            if let Some(user_value) = user_attributes.find_value(hash_attribute) {
+            if let Some(user_value) = options.find_attribute(user_attributes, hash_attribute) {
                 if let Some(user_weight) = HashCode::hash_code(
                     &user_value.to_string(),
                     &filter.get_string("seed", ""),
@@ -21,16 +21,24 @@ impl GrowthbookGateway {
         sdk_key: &str,
         timeout: Duration,
     ) -> Result<Self, GrowthbookError> {
-        Ok(Self {
+        Ok(Self::with_client(url, sdk_key, HttpClient::create_http_client("growthbook", timeout)?))
+    }
+
+    pub fn with_client(
+        url: &str,
+        sdk_key: &str,
+        client: ClientWithMiddleware,
+    ) -> Self {
+        Self {
             url: String::from(url),
             user_agent: format!(
                 "{}/{}",
                 Environment::string_or_default("CARGO_PKG_NAME", "growthbook-rust-sdk"),
                 Environment::string_or_default("CARGO_PKG_VERSION", "1.0.0")
             ),
-            client: HttpClient::create_http_client("growthbook", timeout).map_err(GrowthbookError::from)?,
+            client,
             sdk_key: sdk_key.to_string(),
-        })
+        }
     }
 
     pub async fn get_features(
@@ -40,6 +48,9 @@ impl GrowthbookGateway {
         let sdk = sdk_key.unwrap_or(self.sdk_key.as_str());
         let url = format!("{}/api/features/{}", self.url, sdk);
         let send_result = self.client.get(url).header(USER_AGENT, self.user_agent.clone()).send().await.map_err(GrowthbookError::from)?;
+        if !send_result.status().is_success() {
+            return Err(GrowthbookError::from(send_result));
+        }
 
         let response = send_result.json::<GrowthBookResponse>().await.map_err(GrowthbookError::from)?;
 
@@ -1,27 +1,70 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
+
 use crate::dto::GrowthBookFeature;
-use crate::model_public::{FeatureResult, GrowthBookAttribute};
+use crate::explain::model::FeatureExplanation;
+use crate::layer::model::FeatureOrigin;
+use crate::model_public::{FeatureResult, GrowthBookAttributes};
+use crate::options::{EvaluationOptions, EvaluationScope};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct GrowthBook {
     pub forced_variations: Option<HashMap<String, i64>>,
     pub features: HashMap<String, GrowthBookFeature>,
+    pub date_updated: Option<DateTime<Utc>>,
+    pub(crate) layers: HashMap<String, FeatureOrigin>,
 }
 
 impl GrowthBook {
     pub fn check(
         &self,
         flag_name: &str,
-        option_user_attributes: &Option<Vec<GrowthBookAttribute>>,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationOptions,
     ) -> FeatureResult {
         if let Some(feature) = self.features.get(flag_name) {
-            let user_attributes = &option_user_attributes.clone().unwrap_or_default();
-            feature.get_value(flag_name, vec![], user_attributes, &self.forced_variations, self.features.clone())
+            let options = EvaluationScope::new(options);
+            let result = feature.get_value(flag_name, vec![], user_attributes, &self.forced_variations, &self.features, &options);
+            self.with_layer(flag_name, result)
         } else {
             FeatureResult::unknown_feature()
         }
     }
+
+    pub fn explain(
+        &self,
+        flag_name: &str,
+        user_attributes: &GrowthBookAttributes,
+        options: &EvaluationOptions,
+    ) -> FeatureExplanation {
+        if let Some(feature) = self.features.get(flag_name) {
+            let options = EvaluationScope::new(options);
+            let (rules, result) = feature.explain(flag_name, user_attributes, &self.forced_variations, &self.features, &options);
+            FeatureExplanation {
+                feature_id: String::from(flag_name),
+                found: true,
+                rules,
+                result: self.with_layer(flag_name, result),
+            }
+        } else {
+            FeatureExplanation {
+                feature_id: String::from(flag_name),
+                found: false,
+                rules: vec![],
+                result: FeatureResult::unknown_feature(),
+            }
+        }
+    }
+
+    fn with_layer(
+        &self,
+        flag_name: &str,
+        mut result: FeatureResult,
+    ) -> FeatureResult {
+        result.layer = self.layers.get(flag_name).and_then(|origin| origin.layer(result.rule_index));
+        result
+    }
 }
 
 #[cfg(test)]
@@ -36,7 +79,8 @@ mod test {
     use crate::extensions::JsonHelper;
     use crate::growthbook::GrowthBook;
     use crate::model_public::FeatureResult;
-    use crate::model_public::GrowthBookAttribute;
+    use crate::model_public::{GrowthBookAttribute, GrowthBookAttributes};
+    use crate::options::EvaluationOptions;
 
     #[tokio::test]
     async fn evaluate_get_bucket_range() -> Result<(), Box<dyn std::error::Error>> {
@@ -48,18 +92,48 @@ mod test {
             let gb = GrowthBook {
                 forced_variations: feature.forced_variations.clone(),
                 features: gb_test.features.unwrap_or_default(),
+                date_updated: None,
+                layers: HashMap::new(),
             };
             let user_attributes = feature
                 .attributes
                 .clone()
-                .map(|attr| GrowthBookAttribute::from(attr).expect("Failed to convert to GrowthBookAttribute"));
-            let result = gb.check(feature.feature_name.as_str(), &user_attributes);
+                .map(|attr| GrowthBookAttribute::from(attr).expect("Failed to convert to GrowthBookAttribute"))
+                .unwrap_or_default();
+            let user_attributes = GrowthBookAttributes::new(user_attributes);
+            let result = gb.check(feature.feature_name.as_str(), &user_attributes, &EvaluationOptions::default());
             validate_result(feature, result);
         }
 
         Ok(())
     }
 
+    #[tokio::test]
+    async fn evaluate_explain_with_same_result() -> Result<(), Box<dyn std::error::Error>> {
+        let cases = Cases::new();
+
+        for value in cases.feature {
+            let feature = EvalFeature::new(value);
+            let gb_test = serde_json::from_value::<GrowthBookForTest>(feature.feature.clone()).unwrap_or_else(|_| panic!("Failed to convert to GrowthBookForTest case='{}'", feature.name));
+            let gb = GrowthBook {
+                forced_variations: feature.forced_variations.clone(),
+                features: gb_test.features.unwrap_or_default(),
+                date_updated: None,
+                layers: HashMap::new(),
+            };
+            let user_attributes = feature
+                .attributes
+                .clone()
+                .map(|attr| GrowthBookAttribute::from(attr).expect("Failed to convert to GrowthBookAttribute"))
+                .unwrap_or_default();
+            let user_attributes = GrowthBookAttributes::new(user_attributes);
+            let explanation = gb.explain(feature.feature_name.as_str(), &user_attributes, &EvaluationOptions::default());
+            validate_result(feature, explanation.result);
+        }
+
+        Ok(())
+    }
+
     fn validate_result(
         eval_feature: EvalFeature,
         feature_result: FeatureResult,
@@ -24,11 +24,25 @@ impl HttpClient {
             .default_headers(default_headers)
             .build()
             .map_err(GrowthbookError::from)?;
+        Ok(HttpClient::with_middleware(name, default_config_client))
+    }
+
+    // reuses connections, for a client shared by many SDK keys polling the same hosts
+    pub fn create_keep_alive_http_client(
+        name: &str,
+        timeout_duration: Duration,
+    ) -> Result<ClientWithMiddleware, GrowthbookError> {
+        let default_config_client = Client::builder().timeout(timeout_duration).build().map_err(GrowthbookError::from)?;
+        Ok(HttpClient::with_middleware(name, default_config_client))
+    }
 
-        let client = ClientBuilder::new(default_config_client)
+    fn with_middleware(
+        name: &str,
+        default_config_client: Client,
+    ) -> ClientWithMiddleware {
+        ClientBuilder::new(default_config_client)
             .with_init(Extension(OtelName(String::from(name).into())))
             .with(TracingMiddleware::default())
-            .build();
-        Ok(client)
+            .build()
     }
 }
@@ -0,0 +1,2 @@
+pub mod model;
+pub(crate) mod use_case;
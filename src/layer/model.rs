@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use crate::source::FeatureSource;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayerPrecedence {
+    // the layer feature replaces the underlying feature entirely
+    #[default]
+    Feature,
+    // the layer rules are evaluated before the underlying rules, its default value wins when present
+    Rule,
+}
+
+#[derive(Debug, Clone)]
+pub struct PayloadLayer {
+    pub name: String,
+    pub precedence: LayerPrecedence,
+    pub(crate) source: Arc<dyn FeatureSource>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct FeatureOrigin {
+    pub default_value: Option<String>,
+    pub rules: Vec<Option<String>>,
+}
+
+impl PayloadLayer {
+    pub fn new(
+        name: &str,
+        source: impl FeatureSource + 'static,
+    ) -> Self {
+        PayloadLayer {
+            name: String::from(name),
+            precedence: LayerPrecedence::default(),
+            source: Arc::new(source),
+        }
+    }
+
+    pub fn precedence(
+        mut self,
+        precedence: LayerPrecedence,
+    ) -> Self {
+        self.precedence = precedence;
+        self
+    }
+}
+
+impl FeatureOrigin {
+    pub fn layer(
+        &self,
+        rule_index: Option<usize>,
+    ) -> Option<String> {
+        match rule_index {
+            Some(index) => self.rules.get(index).cloned().flatten(),
+            None => self.default_value.clone(),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use tracing::warn;
+
+use crate::dto::{GrowthBookFeature, GrowthBookResponse};
+use crate::growthbook::GrowthBook;
+use crate::layer::model::{FeatureOrigin, LayerPrecedence, PayloadLayer};
+
+#[derive(Debug, Default)]
+pub(crate) struct LayerStack {
+    layers: Vec<PayloadLayer>,
+    // last payload successfully fetched for each layer, kept when a later fetch fails
+    payloads: Vec<Option<GrowthBookResponse>>,
+    // last base payload applied, layered again when a newer base is not available
+    base: Option<GrowthBookResponse>,
+}
+
+impl LayerStack {
+    pub fn new(layers: Vec<PayloadLayer>) -> Self {
+        let payloads = vec![None; layers.len()];
+        LayerStack { layers, payloads, base: None }
+    }
+
+    pub async fn refresh(&mut self) {
+        for (layer, payload) in self.layers.iter().zip(self.payloads.iter_mut()) {
+            match layer.source.fetch().await {
+                Ok(resp) => *payload = Some(resp),
+                Err(e) => warn!("[growthbook-sdk] Failed to fetch payload layer '{}', keeping last known one: {}", layer.name, e),
+            }
+        }
+    }
+
+    pub fn base(&self) -> Option<&GrowthBookResponse> {
+        self.base.as_ref()
+    }
+
+    pub fn apply(
+        &mut self,
+        base: GrowthBookResponse,
+    ) -> GrowthBook {
+        self.base = Some(base.clone());
+        let mut forced_variations = base.forced_variations;
+        let mut features = base.features;
+        let mut layers: HashMap<String, FeatureOrigin> = HashMap::new();
+
+        for (layer, payload) in self.layers.iter().zip(self.payloads.iter()) {
+            let Some(payload) = payload else {
+                continue;
+            };
+            if let Some(layer_forced_variations) = &payload.forced_variations {
+                forced_variations.get_or_insert_with(HashMap::new).extend(layer_forced_variations.clone());
+            }
+            for (feature_id, layer_feature) in &payload.features {
+                let layer_rules = layer_feature.rules.as_ref().map(Vec::len).unwrap_or(0);
+                let tagged = FeatureOrigin {
+                    default_value: Some(layer.name.clone()),
+                    rules: vec![Some(layer.name.clone()); layer_rules],
+                };
+                match (layer.precedence, features.get(feature_id)) {
+                    (LayerPrecedence::Rule, Some(underlying)) => {
+                        let origin = layers.remove(feature_id).unwrap_or_else(|| FeatureOrigin {
+                            default_value: None,
+                            rules: vec![None; underlying.rules.as_ref().map(Vec::len).unwrap_or(0)],
+                        });
+                        let merged = merge_rules(underlying, layer_feature);
+                        layers.insert(
+                            feature_id.clone(),
+                            FeatureOrigin {
+                                default_value: if layer_feature.default_value.is_some() { tagged.default_value } else { origin.default_value },
+                                rules: tagged.rules.into_iter().chain(origin.rules).collect(),
+                            },
+                        );
+                        features.insert(feature_id.clone(), merged);
+                    },
+                    _ => {
+                        layers.insert(feature_id.clone(), tagged);
+                        features.insert(feature_id.clone(), layer_feature.clone());
+                    },
+                }
+            }
+        }
+
+        GrowthBook {
+            forced_variations,
+            features,
+            date_updated: base.date_updated,
+            layers,
+        }
+    }
+}
+
+fn merge_rules(
+    underlying: &GrowthBookFeature,
+    layer: &GrowthBookFeature,
+) -> GrowthBookFeature {
+    let rules: Vec<_> = layer.rules.iter().flatten().chain(underlying.rules.iter().flatten()).cloned().collect();
+    GrowthBookFeature {
+        default_value: layer.default_value.clone().or_else(|| underlying.default_value.clone()),
+        rules: if rules.is_empty() { None } else { Some(rules) },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::dto::GrowthBookResponse;
+    use crate::layer::model::{LayerPrecedence, PayloadLayer};
+    use crate::layer::use_case::LayerStack;
+    use crate::source::static_source::StaticSource;
+
+    fn payload(value: serde_json::Value) -> GrowthBookResponse {
+        serde_json::from_value(value).expect("Failed to create payload")
+    }
+
+    #[tokio::test]
+    async fn should_replace_feature_and_record_layer() {
+        let base = payload(json!({ "features": { "flag": { "defaultValue": false }, "other": { "defaultValue": 1 } } }));
+        let overlay = StaticSource::new(payload(json!({ "features": { "flag": { "defaultValue": true }, "new-flag": { "defaultValue": "x" } } })));
+        let mut stack = LayerStack::new(vec![PayloadLayer::new("staging", overlay)]);
+        stack.refresh().await;
+
+        let gb = stack.apply(base);
+
+        assert_eq!(Some(json!(true)), gb.features["flag"].default_value);
+        assert_eq!(Some(json!("x")), gb.features["new-flag"].default_value);
+        assert_eq!(Some(String::from("staging")), gb.layers["flag"].layer(None));
+        assert!(!gb.layers.contains_key("other"));
+    }
+
+    #[tokio::test]
+    async fn should_prepend_layer_rules_with_rule_precedence() {
+        let base = payload(json!({ "features": { "flag": { "defaultValue": "base", "rules": [{ "id": "base-rule", "force": "remote" }] } } }));
+        let low = StaticSource::new(payload(json!({ "features": { "flag": { "rules": [{ "id": "low-rule", "force": "low" }] } } })));
+        let high = StaticSource::new(payload(
+            json!({ "features": { "flag": { "defaultValue": "high", "rules": [{ "id": "high-rule", "force": "high" }] } } }),
+        ));
+        let mut stack = LayerStack::new(vec![
+            PayloadLayer::new("low", low).precedence(LayerPrecedence::Rule),
+            PayloadLayer::new("high", high).precedence(LayerPrecedence::Rule),
+        ]);
+        stack.refresh().await;
+
+        let gb = stack.apply(base);
+
+        let rule_ids: Vec<_> = gb.features["flag"].rules.iter().flatten().map(|rule| rule.id()).collect();
+        assert_eq!(vec![Some(String::from("high-rule")), Some(String::from("low-rule")), Some(String::from("base-rule"))], rule_ids);
+        assert_eq!(Some(json!("high")), gb.features["flag"].default_value);
+        let origin = &gb.layers["flag"];
+        assert_eq!(Some(String::from("high")), origin.layer(Some(0)));
+        assert_eq!(Some(String::from("low")), origin.layer(Some(1)));
+        assert_eq!(None, origin.layer(Some(2)));
+        assert_eq!(Some(String::from("high")), origin.layer(None));
+    }
+}
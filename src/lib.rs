@@ -1,9 +1,11 @@
+mod cache;
 pub mod client;
-mod condition;
+pub mod condition;
 pub mod coverage;
 pub mod dto;
 mod env;
 pub mod error;
+pub mod explain;
 mod extensions;
 mod feature;
 pub mod filter;
@@ -11,6 +13,16 @@ mod gateway;
 mod growthbook;
 mod hash;
 mod infra;
+pub mod layer;
+mod macros;
 pub mod model_public;
 pub mod namespace;
+pub mod options;
 mod range;
+pub mod refresh;
+pub mod registry;
+pub mod source;
+pub mod status;
+pub mod updates;
+pub mod watch;
+pub mod webhook;
@@ -0,0 +1,12 @@
+#[macro_export]
+macro_rules! attrs {
+    () => {
+        ::std::vec::Vec::<$crate::model_public::GrowthBookAttribute>::new()
+    };
+    ($($key:expr => $value:expr),+ $(,)?) => {
+        vec![$($crate::model_public::GrowthBookAttribute::new(
+            ::std::string::String::from($key),
+            $crate::model_public::GrowthBookAttributeValue::from($value),
+        )),+]
+    };
+}
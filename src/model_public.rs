@@ -1,13 +1,15 @@
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::ops::Deref;
 
-use regex::Regex;
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{Map, Value};
 
 use crate::error::{GrowthbookError, GrowthbookErrorCode};
-use crate::extensions::JsonHelper;
+use crate::extensions::{FindGrowthBookAttribute, JsonHelper};
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct GrowthBookAttribute {
     pub key: String,
     pub value: GrowthBookAttributeValue,
@@ -20,10 +22,31 @@ pub enum GrowthBookAttributeValue {
     Int(i64),
     Float(f64),
     Bool(bool),
+    DateTime(DateTime<Utc>),
     Array(Vec<GrowthBookAttributeValue>),
     Object(Vec<GrowthBookAttribute>),
 }
 
+#[cfg(feature = "derive")]
+pub use growthbook_rust_sdk_derive::GrowthBookAttributes;
+
+pub trait ToGrowthBookAttributes {
+    fn to_growthbook_attributes(&self) -> Vec<GrowthBookAttribute>;
+
+    fn secure_attribute_keys() -> Vec<String>
+    where
+        Self: Sized,
+    {
+        vec![]
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct GrowthBookAttributes {
+    attributes: Vec<GrowthBookAttribute>,
+    paths: HashMap<String, Vec<usize>>,
+}
+
 #[derive(Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct FeatureResult {
@@ -33,6 +56,9 @@ pub struct FeatureResult {
     pub experiment: Option<Experiment>,
     pub experiment_result: Option<ExperimentResult>,
     pub source: String,
+    pub rule_id: Option<String>,
+    pub rule_index: Option<usize>,
+    pub layer: Option<String>,
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -65,6 +91,8 @@ pub struct ExperimentResult {
     pub bucket: Option<f32>,
     pub key: String,
     pub sticky_bucket_used: bool,
+    pub rule_id: Option<String>,
+    pub rule_index: Option<usize>,
 }
 
 impl GrowthBookAttribute {
@@ -96,16 +124,124 @@ impl GrowthBookAttribute {
     }
 }
 
+impl GrowthBookAttributes {
+    pub fn new(attributes: Vec<GrowthBookAttribute>) -> Self {
+        let mut paths = HashMap::new();
+        index_paths(None, &attributes, &mut vec![], &mut paths);
+        GrowthBookAttributes { attributes, paths }
+    }
+
+    pub fn from(value: Value) -> Result<Self, GrowthbookError> {
+        Ok(GrowthBookAttributes::new(GrowthBookAttribute::from(value)?))
+    }
+
+    pub fn get(
+        &self,
+        path: &str,
+    ) -> Option<&GrowthBookAttributeValue> {
+        match self.paths.get(path) {
+            Some(indexes) => Some(self.resolve(indexes)),
+            None => self.resolve_scalar_prefix(path),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[GrowthBookAttribute] {
+        &self.attributes
+    }
+
+    pub fn into_vec(self) -> Vec<GrowthBookAttribute> {
+        self.attributes
+    }
+
+    fn resolve(
+        &self,
+        indexes: &[usize],
+    ) -> &GrowthBookAttributeValue {
+        let mut attributes = &self.attributes;
+        let mut value = &GrowthBookAttributeValue::Empty;
+        for index in indexes {
+            value = &attributes[*index].value;
+            if let GrowthBookAttributeValue::Object(it) = value {
+                attributes = it;
+            }
+        }
+        value
+    }
+
+    // A path that goes through a scalar resolves to that scalar, as the Vec lookup always did.
+    fn resolve_scalar_prefix(
+        &self,
+        path: &str,
+    ) -> Option<&GrowthBookAttributeValue> {
+        for (position, _) in path.match_indices('.') {
+            match self.paths.get(&path[..position]).map(|indexes| self.resolve(indexes)) {
+                Some(GrowthBookAttributeValue::Object(_)) => continue,
+                Some(GrowthBookAttributeValue::Empty) | None => return None,
+                Some(it) => return Some(it),
+            }
+        }
+        None
+    }
+}
+
+fn index_paths(
+    prefix: Option<&str>,
+    attributes: &[GrowthBookAttribute],
+    indexes: &mut Vec<usize>,
+    paths: &mut HashMap<String, Vec<usize>>,
+) {
+    for (index, attribute) in attributes.iter().enumerate() {
+        if attribute.key.contains('.') {
+            continue;
+        }
+        let path = prefix.map_or_else(|| attribute.key.clone(), |parent| format!("{parent}.{}", attribute.key));
+        if paths.contains_key(&path) {
+            continue;
+        }
+        indexes.push(index);
+        if let GrowthBookAttributeValue::Object(it) = &attribute.value {
+            index_paths(Some(&path), it, indexes, paths);
+        }
+        paths.insert(path, indexes.clone());
+        indexes.pop();
+    }
+}
+
+impl From<Vec<GrowthBookAttribute>> for GrowthBookAttributes {
+    fn from(attributes: Vec<GrowthBookAttribute>) -> Self {
+        GrowthBookAttributes::new(attributes)
+    }
+}
+
+impl Deref for GrowthBookAttributes {
+    type Target = [GrowthBookAttribute];
+
+    fn deref(&self) -> &Self::Target {
+        &self.attributes
+    }
+}
+
+impl FindGrowthBookAttribute for GrowthBookAttributes {
+    fn find_value(
+        &self,
+        attribute_key: &str,
+    ) -> Option<GrowthBookAttributeValue> {
+        self.get(attribute_key).cloned()
+    }
+}
+
 impl GrowthBookAttributeValue {
     pub fn is_number(&self) -> bool {
-        if let Ok(regex) = Regex::new("\\d+") {
-            regex.is_match(&self.to_string().replace('.', ""))
-        } else {
-            false
-        }
+        self.as_f64().is_some()
     }
+
     pub fn as_f64(&self) -> Option<f64> {
-        self.to_string().replace('.', "").parse::<f64>().ok()
+        match self {
+            GrowthBookAttributeValue::Int(it) => Some(*it as f64),
+            GrowthBookAttributeValue::Float(it) => Some(*it),
+            GrowthBookAttributeValue::String(it) => it.trim().parse::<f64>().ok().filter(|number| number.is_finite()),
+            _ => None,
+        }
     }
 
     pub fn to_value(&self) -> Value {
@@ -115,6 +251,7 @@ impl GrowthBookAttributeValue {
             GrowthBookAttributeValue::Int(it) => Value::from(*it),
             GrowthBookAttributeValue::Float(it) => Value::from(*it),
             GrowthBookAttributeValue::Bool(it) => Value::from(*it),
+            GrowthBookAttributeValue::DateTime(it) => Value::from(it.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
             GrowthBookAttributeValue::Array(it) => Value::Array(it.iter().map(|item| item.to_value()).collect()),
             GrowthBookAttributeValue::Object(it) => {
                 let mut map = Map::new();
@@ -157,6 +294,131 @@ impl From<Value> for GrowthBookAttributeValue {
     }
 }
 
+impl From<&str> for GrowthBookAttributeValue {
+    fn from(value: &str) -> Self {
+        GrowthBookAttributeValue::String(String::from(value))
+    }
+}
+
+impl From<String> for GrowthBookAttributeValue {
+    fn from(value: String) -> Self {
+        GrowthBookAttributeValue::String(value)
+    }
+}
+
+impl From<&String> for GrowthBookAttributeValue {
+    fn from(value: &String) -> Self {
+        GrowthBookAttributeValue::String(value.clone())
+    }
+}
+
+impl From<bool> for GrowthBookAttributeValue {
+    fn from(value: bool) -> Self {
+        GrowthBookAttributeValue::Bool(value)
+    }
+}
+
+macro_rules! from_int {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for GrowthBookAttributeValue {
+                fn from(value: $ty) -> Self {
+                    GrowthBookAttributeValue::Int(i64::from(value))
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, u8, u16, u32);
+
+impl From<u64> for GrowthBookAttributeValue {
+    fn from(value: u64) -> Self {
+        match i64::try_from(value) {
+            Ok(it) => GrowthBookAttributeValue::Int(it),
+            Err(_) => GrowthBookAttributeValue::Float(value as f64),
+        }
+    }
+}
+
+impl From<f32> for GrowthBookAttributeValue {
+    fn from(value: f32) -> Self {
+        GrowthBookAttributeValue::Float(f64::from(value))
+    }
+}
+
+impl From<f64> for GrowthBookAttributeValue {
+    fn from(value: f64) -> Self {
+        GrowthBookAttributeValue::Float(value)
+    }
+}
+
+impl From<DateTime<Utc>> for GrowthBookAttributeValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        GrowthBookAttributeValue::DateTime(value)
+    }
+}
+
+impl<T: Into<GrowthBookAttributeValue>> From<Vec<T>> for GrowthBookAttributeValue {
+    fn from(value: Vec<T>) -> Self {
+        GrowthBookAttributeValue::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<GrowthBookAttributeValue>> From<HashMap<K, V>> for GrowthBookAttributeValue {
+    fn from(value: HashMap<K, V>) -> Self {
+        let attributes: Vec<_> = value.into_iter().map(|(key, value)| GrowthBookAttribute::new(key.into(), value.into())).collect();
+        if attributes.is_empty() {
+            GrowthBookAttributeValue::Empty
+        } else {
+            GrowthBookAttributeValue::Object(attributes)
+        }
+    }
+}
+
+impl<T: Into<GrowthBookAttributeValue>> From<Option<T>> for GrowthBookAttributeValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(GrowthBookAttributeValue::Empty, Into::into)
+    }
+}
+
+impl Serialize for GrowthBookAttributeValue {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+// JSON has no date type, so a serialized DateTime comes back as its RFC 3339 String;
+// conditions compare such strings as instants, so evaluation is unchanged
+impl<'de> Deserialize<'de> for GrowthBookAttributeValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(GrowthBookAttributeValue::from)
+    }
+}
+
+impl Serialize for GrowthBookAttributes {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = Map::new();
+        for attribute in &self.attributes {
+            map.insert(attribute.key.clone(), attribute.value.to_value());
+        }
+        map.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GrowthBookAttributes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        GrowthBookAttributes::from(value).map_err(serde::de::Error::custom)
+    }
+}
+
 impl Display for GrowthBookAttributeValue {
     fn fmt(
         &self,
@@ -170,6 +432,7 @@ impl Display for GrowthBookAttributeValue {
             GrowthBookAttributeValue::Int(it) => it.to_string(),
             GrowthBookAttributeValue::Float(it) => it.to_string(),
             GrowthBookAttributeValue::Bool(it) => it.to_string(),
+            GrowthBookAttributeValue::DateTime(it) => it.to_rfc3339_opts(SecondsFormat::AutoSi, true),
         };
 
         write!(f, "{}", message)
@@ -196,7 +459,24 @@ impl FeatureResult {
             experiment: None,
             experiment_result: None,
             source,
+            rule_id: None,
+            rule_index: None,
+            layer: None,
+        }
+    }
+
+    pub fn with_rule(
+        mut self,
+        rule_id: Option<String>,
+        rule_index: usize,
+    ) -> Self {
+        if let Some(experiment_result) = self.experiment_result.as_mut() {
+            experiment_result.rule_id = rule_id.clone();
+            experiment_result.rule_index = Some(rule_index);
         }
+        self.rule_id = rule_id;
+        self.rule_index = Some(rule_index);
+        self
     }
 
     pub fn force(value: Value) -> Self {
@@ -208,6 +488,9 @@ impl FeatureResult {
             experiment: None,
             experiment_result: None,
             source: String::from("force"),
+            rule_id: None,
+            rule_index: None,
+            layer: None,
         }
     }
 
@@ -224,6 +507,9 @@ impl FeatureResult {
             experiment: Some(experiment),
             experiment_result: Some(experiment_result),
             source: String::from("experiment"),
+            rule_id: None,
+            rule_index: None,
+            layer: None,
         }
     }
 
@@ -237,6 +523,9 @@ impl FeatureResult {
             experiment: None,
             experiment_result: None,
             source: String::from("defaultValue"),
+            rule_id: None,
+            rule_index: None,
+            layer: None,
         }
     }
 
@@ -248,6 +537,9 @@ impl FeatureResult {
             experiment: None,
             experiment_result: None,
             source: String::from("prerequisite"),
+            rule_id: None,
+            rule_index: None,
+            layer: None,
         }
     }
 
@@ -259,6 +551,23 @@ impl FeatureResult {
             experiment: None,
             experiment_result: None,
             source: String::from("cyclicPrerequisite"),
+            rule_id: None,
+            rule_index: None,
+            layer: None,
+        }
+    }
+
+    pub fn fail_closed() -> Self {
+        Self {
+            value: Value::Null,
+            on: false,
+            off: true,
+            experiment: None,
+            experiment_result: None,
+            source: String::from("failClosed"),
+            rule_id: None,
+            rule_index: None,
+            layer: None,
         }
     }
 
@@ -270,6 +579,9 @@ impl FeatureResult {
             experiment: None,
             experiment_result: None,
             source: String::from("unknownFeature"),
+            rule_id: None,
+            rule_index: None,
+            layer: None,
         }
     }
 }
@@ -277,7 +589,7 @@ impl FeatureResult {
 fn is_on(value: &Value) -> bool {
     let is_on = if value.is_null() {
         false
-    } else if (value.is_number() && value.force_f64(-1.0) != 0.0) || (value.is_string() && value.force_string("any") != "") {
+    } else if (value.is_number() && value.force_f64(-1.0) != 0.0) || (value.is_string() && !value.force_string("any").is_empty()) {
         true
     } else if value.is_boolean() {
         value.as_bool().unwrap_or(false)
@@ -290,3 +602,136 @@ fn is_on(value: &Value) -> bool {
     };
     is_on
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    use crate::extensions::FindGrowthBookAttribute;
+    use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue, GrowthBookAttributes};
+
+    #[test]
+    fn find_same_values_as_vec_lookup() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({
+            "id": "123",
+            "empty": null,
+            "company": { "name": "acme", "address": { "country": "BR" }, "size": 10 },
+            "tags": ["a", "b"],
+        });
+        let vec = GrowthBookAttribute::from(value.clone())?;
+        let container = GrowthBookAttributes::from(value)?;
+
+        for path in [
+            "id",
+            "id.nested",
+            "empty",
+            "empty.nested",
+            "company",
+            "company.name",
+            "company.address.country",
+            "company.address.city",
+            "company.size.value",
+            "tags",
+            "missing",
+            "missing.nested",
+        ] {
+            assert_eq!(vec.find_value(path), container.find_value(path), "lookup failed: {path}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn keep_first_attribute_with_duplicated_key() {
+        let container = GrowthBookAttributes::new(vec![
+            GrowthBookAttribute::new(String::from("id"), GrowthBookAttributeValue::Int(1)),
+            GrowthBookAttribute::new(String::from("id"), GrowthBookAttributeValue::Int(2)),
+        ]);
+
+        assert_eq!(Some(&GrowthBookAttributeValue::Int(1)), container.get("id"));
+        assert_eq!(2, container.len());
+    }
+
+    #[test]
+    fn convert_primitives_into_values() {
+        assert_eq!(GrowthBookAttributeValue::String(String::from("pro")), GrowthBookAttributeValue::from("pro"));
+        assert_eq!(GrowthBookAttributeValue::Int(42), GrowthBookAttributeValue::from(42u8));
+        assert_eq!(GrowthBookAttributeValue::Int(-42), GrowthBookAttributeValue::from(-42i64));
+        assert_eq!(GrowthBookAttributeValue::Float(u64::MAX as f64), GrowthBookAttributeValue::from(u64::MAX));
+        assert_eq!(GrowthBookAttributeValue::Float(1.5), GrowthBookAttributeValue::from(1.5f32));
+        assert_eq!(GrowthBookAttributeValue::Bool(true), GrowthBookAttributeValue::from(true));
+        assert_eq!(GrowthBookAttributeValue::Empty, GrowthBookAttributeValue::from(None::<String>));
+        assert_eq!(GrowthBookAttributeValue::Int(7), GrowthBookAttributeValue::from(Some(7)));
+        assert_eq!(
+            GrowthBookAttributeValue::Array(vec![GrowthBookAttributeValue::from("a"), GrowthBookAttributeValue::from("b")]),
+            GrowthBookAttributeValue::from(vec!["a", "b"])
+        );
+    }
+
+    #[test]
+    fn convert_map_into_object() {
+        let value = GrowthBookAttributeValue::from(HashMap::from([("country", "BR")]));
+
+        assert_eq!(
+            GrowthBookAttributeValue::Object(vec![GrowthBookAttribute::new(String::from("country"), GrowthBookAttributeValue::from("BR"))]),
+            value
+        );
+        assert_eq!(GrowthBookAttributeValue::Empty, GrowthBookAttributeValue::from(HashMap::<String, i64>::new()));
+    }
+
+    #[test]
+    fn build_attributes_with_macro() {
+        let plan = String::from("pro");
+
+        let attributes = crate::attrs! {
+            "id" => 123,
+            "plan" => &plan,
+            "beta" => true,
+            "tags" => vec!["a", "b"],
+            "referrer" => None::<String>,
+        };
+
+        assert_eq!(
+            GrowthBookAttribute::from(json!({ "id": 123, "plan": "pro", "beta": true, "tags": ["a", "b"], "referrer": null })).expect("Failed to create attributes"),
+            attributes
+        );
+        assert!(crate::attrs! {}.is_empty());
+    }
+
+    #[test]
+    fn serialize_and_deserialize_attributes() -> Result<(), Box<dyn std::error::Error>> {
+        let attributes = crate::attrs! {
+            "id" => "123",
+            "company" => HashMap::from([("size", 10)]),
+            "signup" => Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).single().expect("Failed to create date time"),
+        };
+
+        let container = GrowthBookAttributes::new(attributes.clone());
+        let serialized = serde_json::to_value(&container)?;
+        assert_eq!(json!({ "id": "123", "company": { "size": 10 }, "signup": "2024-01-01T00:00:00Z" }), serialized);
+
+        let deserialized: GrowthBookAttributes = serde_json::from_value(serialized)?;
+        let mut expected = attributes;
+        expected[2].value = GrowthBookAttributeValue::String(String::from("2024-01-01T00:00:00Z"));
+        assert_eq!(GrowthBookAttributes::new(expected), deserialized);
+        assert_eq!(Some(&GrowthBookAttributeValue::Int(10)), deserialized.get("company.size"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_and_deserialize_attribute() -> Result<(), Box<dyn std::error::Error>> {
+        let attribute = GrowthBookAttribute::new(String::from("company"), GrowthBookAttributeValue::from(HashMap::from([("size", 10)])));
+
+        let serialized = serde_json::to_value(&attribute)?;
+        assert_eq!(json!({ "key": "company", "value": { "size": 10 } }), serialized);
+
+        let deserialized: GrowthBookAttribute = serde_json::from_value(serialized)?;
+        assert_eq!(attribute, deserialized);
+
+        Ok(())
+    }
+}
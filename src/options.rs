@@ -0,0 +1,257 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::extensions::FindGrowthBookAttribute;
+use crate::model_public::{GrowthBookAttribute, GrowthBookAttributeValue, GrowthBookAttributes};
+
+pub type DiagnosticsHook = Arc<dyn Fn(&EvaluationDiagnostic) + Send + Sync>;
+
+pub trait CustomOperator: Send + Sync {
+    fn matches(
+        &self,
+        user_value: Option<&GrowthBookAttributeValue>,
+        condition_value: &GrowthBookAttributeValue,
+    ) -> bool;
+}
+
+impl<F> CustomOperator for F
+where
+    F: Fn(Option<&GrowthBookAttributeValue>, &GrowthBookAttributeValue) -> bool + Send + Sync,
+{
+    fn matches(
+        &self,
+        user_value: Option<&GrowthBookAttributeValue>,
+        condition_value: &GrowthBookAttributeValue,
+    ) -> bool {
+        self(user_value, condition_value)
+    }
+}
+
+pub trait AttributeProvider: Send + Sync {
+    fn provide(
+        &self,
+        user_attributes: &[GrowthBookAttribute],
+    ) -> Option<GrowthBookAttributeValue>;
+}
+
+impl<F> AttributeProvider for F
+where
+    F: Fn(&[GrowthBookAttribute]) -> Option<GrowthBookAttributeValue> + Send + Sync,
+{
+    fn provide(
+        &self,
+        user_attributes: &[GrowthBookAttribute],
+    ) -> Option<GrowthBookAttributeValue> {
+        self(user_attributes)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct EvaluationOptions {
+    pub secure_attributes: Option<SecureAttributes>,
+    pub diagnostics: Option<DiagnosticsHook>,
+    pub custom_operators: HashMap<String, Arc<dyn CustomOperator>>,
+    pub attribute_providers: HashMap<String, Arc<dyn AttributeProvider>>,
+}
+
+// The options of a single flag check, computed attributes are memoized so each one is resolved at most once per evaluation.
+pub struct EvaluationScope<'a> {
+    options: &'a EvaluationOptions,
+    resolved_attributes: RefCell<HashMap<String, Option<GrowthBookAttributeValue>>>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum EvaluationDiagnostic {
+    #[serde(rename_all = "camelCase")]
+    UnknownOperator { operator: String, attribute: Option<String> },
+    #[serde(rename_all = "camelCase")]
+    ConfigDeserializationFailed { feature: String, message: String },
+    #[serde(rename_all = "camelCase")]
+    PayloadReloadFailed { path: String, message: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct SecureAttributes {
+    pub salt: String,
+    pub keys: HashSet<String>,
+}
+
+impl EvaluationOptions {
+    pub fn report(
+        &self,
+        diagnostic: EvaluationDiagnostic,
+    ) {
+        match &self.diagnostics {
+            Some(hook) => hook(&diagnostic),
+            None => warn!("[growthbook-sdk] {:?}", diagnostic),
+        }
+    }
+
+    pub fn add_custom_operator(
+        &mut self,
+        name: &str,
+        operator: Arc<dyn CustomOperator>,
+    ) {
+        let name = if name.starts_with('$') { String::from(name) } else { format!("${name}") };
+        self.custom_operators.insert(name, operator);
+    }
+
+    pub fn custom_operator(
+        &self,
+        name: &str,
+    ) -> Option<&Arc<dyn CustomOperator>> {
+        self.custom_operators.get(name)
+    }
+
+    pub fn add_attribute_provider(
+        &mut self,
+        name: &str,
+        provider: Arc<dyn AttributeProvider>,
+    ) {
+        self.attribute_providers.insert(String::from(name), provider);
+    }
+}
+
+impl<'a> EvaluationScope<'a> {
+    pub fn new(options: &'a EvaluationOptions) -> Self {
+        EvaluationScope {
+            options,
+            resolved_attributes: RefCell::default(),
+        }
+    }
+
+    pub(crate) fn find_attribute(
+        &self,
+        user_attributes: &GrowthBookAttributes,
+        attribute_key: &str,
+    ) -> Option<GrowthBookAttributeValue> {
+        user_attributes.find_value(attribute_key).or_else(|| self.provide_attribute(user_attributes, attribute_key))
+    }
+
+    fn provide_attribute(
+        &self,
+        user_attributes: &[GrowthBookAttribute],
+        attribute_key: &str,
+    ) -> Option<GrowthBookAttributeValue> {
+        let (name, path) = attribute_key.split_once('.').map_or((attribute_key, None), |(name, path)| (name, Some(path)));
+        let provider = self.options.attribute_providers.get(name)?;
+        let cached = self.resolved_attributes.borrow().get(name).cloned();
+        let value = match cached {
+            Some(it) => it,
+            None => {
+                let value = provider.provide(user_attributes);
+                self.resolved_attributes.borrow_mut().insert(String::from(name), value.clone());
+                value
+            },
+        }?;
+        match (path, value) {
+            (None, value) => Some(value),
+            (Some(path), GrowthBookAttributeValue::Object(it)) => it.find_value(path),
+            (Some(_), _) => None,
+        }
+    }
+}
+
+impl Deref for EvaluationScope<'_> {
+    type Target = EvaluationOptions;
+
+    fn deref(&self) -> &Self::Target {
+        self.options
+    }
+}
+
+impl Debug for EvaluationOptions {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("EvaluationOptions")
+            .field("secure_attributes", &self.secure_attributes)
+            .field("diagnostics", &self.diagnostics.as_ref().map(|_| "Fn(&EvaluationDiagnostic)"))
+            .field("custom_operators", &self.custom_operators.keys().collect::<Vec<_>>())
+            .field("attribute_providers", &self.attribute_providers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl SecureAttributes {
+    pub fn new(
+        salt: &str,
+        keys: Vec<String>,
+    ) -> Self {
+        SecureAttributes {
+            salt: String::from(salt),
+            keys: keys.into_iter().collect(),
+        }
+    }
+
+    pub fn is_secure(
+        &self,
+        attribute_key: &str,
+    ) -> bool {
+        self.keys.contains(attribute_key)
+    }
+
+    pub fn hash(
+        &self,
+        value: &GrowthBookAttributeValue,
+    ) -> GrowthBookAttributeValue {
+        match value {
+            GrowthBookAttributeValue::String(it) => GrowthBookAttributeValue::String(self.hash_string(it)),
+            GrowthBookAttributeValue::Array(it) => GrowthBookAttributeValue::Array(it.iter().map(|item| self.hash(item)).collect()),
+            it => it.clone(),
+        }
+    }
+
+    fn hash_string(
+        &self,
+        value: &str,
+    ) -> String {
+        let digest = Sha256::digest(format!("{}{}", self.salt, value).as_bytes());
+        format!("{:x}", digest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model_public::GrowthBookAttributeValue;
+    use crate::options::SecureAttributes;
+
+    #[test]
+    fn should_hash_strings_with_salt() {
+        let secure = SecureAttributes::new("salt", vec![String::from("email")]);
+
+        let hashed = secure.hash(&GrowthBookAttributeValue::String(String::from("user@example.com")));
+
+        assert_eq!(
+            GrowthBookAttributeValue::String(String::from("52eda3273bc1a0a8799f962a5cc19d6ba8421be05ccce08f4c45e201a08ff86b")),
+            hashed
+        );
+    }
+
+    #[test]
+    fn should_hash_each_string_of_array() {
+        let secure = SecureAttributes::new("", vec![]);
+
+        let hashed = secure.hash(&GrowthBookAttributeValue::Array(vec![
+            GrowthBookAttributeValue::String(String::from("a")),
+            GrowthBookAttributeValue::Int(1),
+        ]));
+
+        assert_eq!(
+            GrowthBookAttributeValue::Array(vec![
+                GrowthBookAttributeValue::String(String::from("ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb")),
+                GrowthBookAttributeValue::Int(1),
+            ]),
+            hashed
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod model;
+pub(crate) mod use_case;
+pub(crate) mod watcher;
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    // attempts per refresh cycle, including the first one
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    // growth of the backoff per attempt, values below 1.0 (or NaN) are treated as 1.0
+    pub multiplier: f64,
+    // fraction of each backoff that is randomized, clamped to 0.0..=1.0 (NaN disables it)
+    pub jitter: f64,
+    // fraction of the update interval that is randomized, clamped to 0.0..=1.0 (NaN disables it)
+    pub interval_jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.5,
+            interval_jitter: 0.1,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn no_retry() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    pub fn backoff(
+        &self,
+        attempt: u32,
+        retry_after: Option<Duration>,
+    ) -> Duration {
+        // f64::max ignores NaN, so a NaN multiplier becomes 1.0
+        let multiplier = self.multiplier.max(1.0);
+        let exponential = self.initial_backoff.as_secs_f64() * multiplier.powi(attempt.saturating_sub(1).min(i32::MAX as u32) as i32);
+        let backoff = Duration::try_from_secs_f64(exponential.min(self.max_backoff.as_secs_f64())).unwrap_or(self.max_backoff);
+        jittered(backoff, self.jitter).max(retry_after.unwrap_or_default())
+    }
+
+    pub fn interval(
+        &self,
+        interval: Duration,
+        retry_after: Option<Duration>,
+    ) -> Duration {
+        jittered(interval, self.interval_jitter).max(retry_after.unwrap_or_default())
+    }
+}
+
+// removes up to `jitter` of the duration so concurrent clients spread out without exceeding it
+fn jittered(
+    duration: Duration,
+    jitter: f64,
+) -> Duration {
+    let jitter = if jitter.is_nan() { 0.0 } else { jitter.clamp(0.0, 1.0) };
+    if jitter == 0.0 {
+        return duration;
+    }
+    // the f64 round trip can overshoot near Duration::MAX, which would panic in mul_f64
+    Duration::try_from_secs_f64(duration.as_secs_f64() * (1.0 - jitter * rand::rng().random::<f64>()))
+        .unwrap_or(duration)
+        .min(duration)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::refresh::model::RetryPolicy;
+
+    #[test]
+    fn should_grow_backoff_exponentially_up_to_max() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            max_backoff: Duration::from_secs(5),
+            ..RetryPolicy::default()
+        };
+
+        let backoffs: Vec<_> = (1..=5).map(|attempt| policy.backoff(attempt, None)).collect();
+
+        assert_eq!(vec![1, 2, 4, 5, 5], backoffs.iter().map(Duration::as_secs).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn should_keep_jittered_durations_within_bounds() {
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            interval_jitter: 0.2,
+            ..RetryPolicy::default()
+        };
+
+        for _ in 0..100 {
+            let backoff = policy.backoff(2, None);
+            assert!(backoff >= Duration::from_secs(1) && backoff <= Duration::from_secs(2));
+            let interval = policy.interval(Duration::from_secs(60), None);
+            assert!(interval >= Duration::from_secs(48) && interval <= Duration::from_secs(60));
+        }
+    }
+
+    #[test]
+    fn should_clamp_out_of_range_values() {
+        let policy = RetryPolicy {
+            multiplier: 0.5,
+            jitter: -1.0,
+            interval_jitter: 2.0,
+            ..RetryPolicy::default()
+        };
+
+        assert_eq!(Duration::from_secs(1), policy.backoff(3, None));
+        for _ in 0..100 {
+            assert!(policy.interval(Duration::from_secs(60), None) <= Duration::from_secs(60));
+        }
+    }
+
+    #[test]
+    fn should_not_panic_on_nan_or_infinite_values() {
+        let policy = RetryPolicy {
+            multiplier: f64::NAN,
+            jitter: f64::NAN,
+            interval_jitter: f64::NAN,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(Duration::from_secs(1), policy.backoff(3, None));
+        assert_eq!(Duration::from_secs(60), policy.interval(Duration::from_secs(60), None));
+
+        let policy = RetryPolicy {
+            multiplier: f64::INFINITY,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::MAX,
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(Duration::ZERO, policy.backoff(1, None));
+        assert_eq!(Duration::MAX, policy.backoff(u32::MAX, None));
+        assert!(RetryPolicy::default().interval(Duration::MAX, None) <= Duration::MAX);
+    }
+
+    #[test]
+    fn should_wait_at_least_retry_after() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.backoff(1, Some(Duration::from_secs(120))) >= Duration::from_secs(120));
+        assert!(policy.interval(Duration::from_secs(60), Some(Duration::from_secs(300))) >= Duration::from_secs(300));
+    }
+}
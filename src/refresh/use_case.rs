@@ -0,0 +1,138 @@
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+use tracing::{error, warn};
+
+use crate::cache::PayloadCache;
+use crate::dto::GrowthBookResponse;
+use crate::error::GrowthbookError;
+use crate::growthbook::GrowthBook;
+use crate::layer::use_case::LayerStack;
+use crate::refresh::model::RetryPolicy;
+use crate::source::FeatureSource;
+use crate::status::StatusTracker;
+use crate::updates::model::PayloadDiff;
+use crate::updates::notifier::PayloadNotifier;
+
+#[derive(Debug)]
+pub(crate) struct Refresher {
+    pub source: Arc<dyn FeatureSource>,
+    // also serializes refreshes triggered by the interval task and by file changes
+    pub layers: Mutex<LayerStack>,
+    pub config: Arc<RwLock<GrowthBook>>,
+    pub notifier: Arc<PayloadNotifier>,
+    pub cache: Option<PayloadCache>,
+    pub status: Arc<StatusTracker>,
+}
+
+impl Refresher {
+    pub async fn refresh(&self) -> Result<bool, GrowthbookError> {
+        let mut layers = self.layers.lock().await;
+        let new_config = self.source.fetch().await.inspect_err(|e| self.status.record_failure(e))?;
+        layers.refresh().await;
+        let base = match self.older_than_current(&new_config) {
+            Some(current) => {
+                warn!(
+                    "[growthbook-sdk] Skipping payload updated at {:?}, older than the current one updated at {}",
+                    new_config.date_updated, current
+                );
+                self.status.record_skipped(&new_config);
+                // the layers are still refreshed on top of the current base payload
+                match layers.base() {
+                    Some(base) => base.clone(),
+                    None => return Ok(false),
+                }
+            },
+            None => {
+                save_cache(&self.cache, &new_config).await;
+                self.status.record_success(&new_config);
+                new_config
+            },
+        };
+        let updated_features = layers.apply(base);
+        let mut writable_config = self.config.write().unwrap_or_else(|e| {
+            warn!("[growthbook-sdk] gb data lock was poisoned, replacing the recovered features");
+            self.config.clear_poison();
+            e.into_inner()
+        });
+        let diff = PayloadDiff::between(&writable_config.features, &updated_features.features).with_forced_variations(&writable_config.forced_variations, &updated_features.forced_variations);
+        *writable_config = updated_features;
+        drop(writable_config);
+        let changed = !diff.is_empty();
+        self.notifier.notify(diff);
+        Ok(changed)
+    }
+
+    // a lagging CDN edge or proxy may serve a payload older than the one already applied
+    fn older_than_current(
+        &self,
+        payload: &GrowthBookResponse,
+    ) -> Option<DateTime<Utc>> {
+        let current = self.config.read().unwrap_or_else(PoisonError::into_inner).date_updated?;
+        payload.date_updated.filter(|date_updated| *date_updated < current).map(|_| current)
+    }
+
+    pub fn stale_in(&self) -> Option<Duration> {
+        self.status.stale_in()
+    }
+
+    pub fn check_staleness(&self) {
+        let feature_count = self.config.read().unwrap_or_else(PoisonError::into_inner).features.len();
+        self.status.check_staleness(feature_count);
+    }
+}
+
+pub(crate) async fn updated_features_task(
+    refresher: Arc<Refresher>,
+    retry_policy: RetryPolicy,
+    interval: Duration,
+) {
+    loop {
+        let retry_after = refresh_with_retry(&refresher, &retry_policy).await;
+        refresher.check_staleness();
+        sleep(retry_policy.interval(interval, retry_after)).await;
+    }
+}
+
+// wakes up when the payload goes stale, also while a refresh is backing off
+pub(crate) async fn staleness_task(refresher: Arc<Refresher>) {
+    while let Some(stale_in) = refresher.stale_in() {
+        sleep(stale_in).await;
+        refresher.check_staleness();
+    }
+}
+
+// returns the Retry-After of the last failed attempt when the whole cycle failed
+pub(crate) async fn refresh_with_retry(
+    refresher: &Refresher,
+    retry_policy: &RetryPolicy,
+) -> Option<Duration> {
+    let max_attempts = retry_policy.max_attempts.max(1);
+    for attempt in 1..=max_attempts {
+        match refresher.refresh().await {
+            Ok(_) => return None,
+            Err(e) => {
+                error!("[growthbook-sdk] Failed to fetch features from server (attempt {}/{}): {:?}", attempt, max_attempts, e);
+                if attempt == max_attempts {
+                    return e.retry_after;
+                }
+                sleep(retry_policy.backoff(attempt, e.retry_after)).await;
+            },
+        }
+    }
+    None
+}
+
+pub(crate) async fn save_cache(
+    cache: &Option<PayloadCache>,
+    payload: &GrowthBookResponse,
+) {
+    if let Some(cache) = cache {
+        if let Err(e) = cache.save(payload).await {
+            error!("[growthbook-sdk] {}", e);
+        }
+    }
+}
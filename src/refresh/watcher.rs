@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::sleep;
+use tracing::{error, info};
+
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+use crate::options::{EvaluationDiagnostic, EvaluationOptions};
+use crate::refresh::use_case::Refresher;
+
+// editors and ConfigMap updates emit bursts of events for a single change
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+// the watcher stops once the returned sender is dropped or the refresher is gone
+pub(crate) fn watch_file(
+    refresher: Weak<Refresher>,
+    options: Arc<EvaluationOptions>,
+    path: &Path,
+) -> Result<oneshot::Sender<()>, GrowthbookError> {
+    let path = std::path::absolute(path).map_err(|e| watch_error(path, e))?;
+    // the parent directory is watched since ConfigMaps and atomic writes replace the file instead of modifying it
+    let directory = path.parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("/"));
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let watched = path.clone();
+    let mut watcher = RecommendedWatcher::new(
+        move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                if event.paths.iter().any(|it| is_relevant(&watched, it)) {
+                    let _ = sender.send(());
+                }
+            }
+        },
+        notify::Config::default(),
+    )
+    .map_err(|e| watch_error(&path, e))?;
+    watcher.watch(&directory, RecursiveMode::NonRecursive).map_err(|e| watch_error(&path, e))?;
+
+    let (stop, mut stopped) = oneshot::channel();
+    tokio::spawn(async move {
+        // keeps the watcher alive as long as the task runs
+        let _watcher = watcher;
+        loop {
+            tokio::select! {
+                _ = &mut stopped => break,
+                event = receiver.recv() => {
+                    if event.is_none() {
+                        break;
+                    }
+                    sleep(DEBOUNCE).await;
+                    while receiver.try_recv().is_ok() {}
+                    let Some(refresher) = refresher.upgrade() else {
+                        break;
+                    };
+                    match refresher.refresh().await {
+                        Ok(true) => info!("[growthbook-sdk] Reloaded features from {:?}", path),
+                        Ok(false) => {},
+                        Err(e) => {
+                            error!("[growthbook-sdk] Failed to reload features from {:?}, keeping last good payload: {}", path, e);
+                            options.report(EvaluationDiagnostic::PayloadReloadFailed {
+                                path: path.display().to_string(),
+                                message: e.message,
+                            });
+                        },
+                    }
+                }
+            }
+        }
+    });
+    Ok(stop)
+}
+
+fn is_relevant(
+    watched: &Path,
+    changed: &Path,
+) -> bool {
+    // kubernetes swaps the `..data` symlink when a ConfigMap changes
+    changed == watched || changed.file_name().and_then(|it| it.to_str()).is_some_and(|it| it.starts_with(".."))
+}
+
+fn watch_error(
+    path: &Path,
+    error: impl std::fmt::Display,
+) -> GrowthbookError {
+    GrowthbookError::new(GrowthbookErrorCode::FileWatcher, &format!("Failed to watch features file {:?}: {}", path, error))
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::future::poll_fn;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, Weak};
+use std::time::{Duration, Instant};
+
+use reqwest_middleware::ClientWithMiddleware;
+use tokio::sync::{mpsc, OnceCell};
+use tokio::task::JoinSet;
+use tokio_util::time::DelayQueue;
+use tracing::{error, info};
+
+use crate::client::{GrowthBookClient, GrowthBookClientBuilder};
+use crate::env::Environment;
+use crate::error::GrowthbookError;
+use crate::infra::HttpClient;
+use crate::refresh::model::RetryPolicy;
+use crate::refresh::use_case::refresh_with_retry;
+
+#[derive(Debug, Clone)]
+pub struct ClientRegistry {
+    inner: Arc<RegistryInner>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClientRegistryBuilder {
+    update_interval: Option<Duration>,
+    http_timeout: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RegistryKey {
+    pub api_url: String,
+    pub sdk_key: String,
+}
+
+#[derive(Debug)]
+struct RegistryInner {
+    http_client: ClientWithMiddleware,
+    update_interval: Duration,
+    idle_timeout: Duration,
+    retry_policy: RetryPolicy,
+    clients: Mutex<HashMap<RegistryKey, RegistryEntry>>,
+    // started with the first client, since the registry may be built outside a runtime
+    scheduler: OnceLock<mpsc::UnboundedSender<ScheduledClient>>,
+}
+
+#[derive(Debug)]
+struct RegistryEntry {
+    // initialized outside the map lock so a slow tenant does not block the others
+    client: Arc<OnceCell<GrowthBookClient>>,
+    last_used: Instant,
+}
+
+#[derive(Debug)]
+struct ScheduledClient {
+    key: RegistryKey,
+    client: Arc<OnceCell<GrowthBookClient>>,
+}
+
+impl ClientRegistryBuilder {
+    pub fn update_interval(
+        mut self,
+        update_interval: Duration,
+    ) -> Self {
+        self.update_interval = Some(update_interval);
+        self
+    }
+
+    pub fn http_timeout(
+        mut self,
+        http_timeout: Duration,
+    ) -> Self {
+        self.http_timeout = Some(http_timeout);
+        self
+    }
+
+    pub fn idle_timeout(
+        mut self,
+        idle_timeout: Duration,
+    ) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    pub fn retry_policy(
+        mut self,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn build(self) -> Result<ClientRegistry, GrowthbookError> {
+        let update_interval = self.update_interval.unwrap_or_else(|| Duration::from_secs(Environment::u64_or_default("GB_UPDATE_INTERVAL", 60)));
+        let http_timeout = self.http_timeout.unwrap_or_else(|| Duration::from_secs(Environment::u64_or_default("GB_HTTP_CLIENT_TIMEOUT", 10)));
+        Ok(ClientRegistry {
+            inner: Arc::new(RegistryInner {
+                http_client: HttpClient::create_keep_alive_http_client("growthbook", http_timeout)?,
+                update_interval,
+                idle_timeout: self.idle_timeout.unwrap_or(Duration::from_secs(30 * 60)),
+                retry_policy: self.retry_policy,
+                clients: Mutex::new(HashMap::new()),
+                scheduler: OnceLock::new(),
+            }),
+        })
+    }
+}
+
+impl ClientRegistry {
+    pub fn builder() -> ClientRegistryBuilder {
+        ClientRegistryBuilder::default()
+    }
+
+    // creates the client on first use, clients are only evicted once idle and no longer held outside the registry
+    pub async fn client(
+        &self,
+        api_url: &str,
+        sdk_key: &str,
+    ) -> Result<GrowthBookClient, GrowthbookError> {
+        let key = RegistryKey {
+            api_url: String::from(api_url),
+            sdk_key: String::from(sdk_key),
+        };
+        let cell = {
+            let mut clients = self.inner.lock_clients();
+            let entry = clients.entry(key.clone()).or_insert_with(|| {
+                let client = Arc::new(OnceCell::new());
+                self.schedule(ScheduledClient { key, client: Arc::clone(&client) });
+                RegistryEntry { client, last_used: Instant::now() }
+            });
+            entry.last_used = Instant::now();
+            Arc::clone(&entry.client)
+        };
+        let client = cell
+            .get_or_try_init(|| {
+                info!("[growthbook-sdk] Creating client for {} in registry", api_url);
+                GrowthBookClientBuilder::new(api_url, sdk_key).scheduled_by_registry(self.inner.http_client.clone()).build()
+            })
+            .await?;
+        Ok(client.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock_clients().values().filter(|entry| entry.client.initialized()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // a removed client that is still held keeps being refreshed until its last clone is dropped
+    pub fn remove(
+        &self,
+        api_url: &str,
+        sdk_key: &str,
+    ) -> bool {
+        let key = RegistryKey {
+            api_url: String::from(api_url),
+            sdk_key: String::from(sdk_key),
+        };
+        self.inner.lock_clients().remove(&key).is_some()
+    }
+
+    fn schedule(
+        &self,
+        client: ScheduledClient,
+    ) {
+        let scheduler = self.inner.scheduler.get_or_init(|| {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            tokio::spawn(refresh_scheduler(Arc::downgrade(&self.inner), receiver, self.inner.retry_policy.clone(), self.inner.update_interval));
+            sender
+        });
+        // the scheduler only stops once the registry is dropped
+        let _ = scheduler.send(client);
+    }
+}
+
+impl RegistryInner {
+    fn lock_clients(&self) -> MutexGuard<'_, HashMap<RegistryKey, RegistryEntry>> {
+        self.clients.lock().unwrap_or_else(|e| {
+            self.clients.clear_poison();
+            e.into_inner()
+        })
+    }
+
+    // false once the client was evicted while idle, or removed or replaced and no longer held, which stops its refreshes
+    fn keep_scheduled(
+        &self,
+        scheduled: &ScheduledClient,
+        held: bool,
+    ) -> bool {
+        let mut clients = self.lock_clients();
+        let Some(entry) = clients.get(&scheduled.key).filter(|entry| Arc::ptr_eq(&entry.client, &scheduled.client)) else {
+            return held;
+        };
+        if entry.last_used.elapsed() >= self.idle_timeout && !held {
+            clients.remove(&scheduled.key);
+            info!("[growthbook-sdk] Evicted idle client for {} from registry", scheduled.key.api_url);
+            return false;
+        }
+        true
+    }
+}
+
+// a single task drives the deadline of every client, while their refreshes run concurrently so a slow or backing-off tenant does not delay the others
+async fn refresh_scheduler(
+    registry: Weak<RegistryInner>,
+    mut scheduled: mpsc::UnboundedReceiver<ScheduledClient>,
+    retry_policy: RetryPolicy,
+    update_interval: Duration,
+) {
+    let mut deadlines = DelayQueue::new();
+    let mut refreshes = JoinSet::new();
+    loop {
+        tokio::select! {
+            Some(client) = scheduled.recv() => {
+                deadlines.insert(client, retry_policy.interval(update_interval, None));
+            },
+            Some(expired) = poll_fn(|cx| deadlines.poll_expired(cx)) => {
+                let client = expired.into_inner();
+                let held = client.client.get().is_some_and(GrowthBookClient::is_shared);
+                if !registry.upgrade().map_or(held, |inner| inner.keep_scheduled(&client, held)) {
+                    continue;
+                }
+                match client.client.get().cloned() {
+                    Some(growthbook) => {
+                        let retry_policy = retry_policy.clone();
+                        refreshes.spawn(async move {
+                            let retry_after = refresh_with_retry(growthbook.refresher(), &retry_policy).await;
+                            growthbook.refresher().check_staleness();
+                            (client, retry_after)
+                        });
+                    },
+                    // still being created, or failed to be and is retried by the next lookup
+                    None => {
+                        deadlines.insert(client, retry_policy.interval(update_interval, None));
+                    },
+                }
+            },
+            Some(refreshed) = refreshes.join_next() => match refreshed {
+                Ok((client, retry_after)) => {
+                    deadlines.insert(client, retry_policy.interval(update_interval, retry_after));
+                },
+                Err(e) => error!("[growthbook-sdk] Registry refresh task failed: {:?}", e),
+            },
+            // the registry is gone and no held client is left
+            else => return,
+        }
+    }
+}
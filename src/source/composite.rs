@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::dto::GrowthBookResponse;
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+use crate::source::FeatureSource;
+
+#[derive(Debug, Clone, Default)]
+pub struct CompositeSource {
+    sources: Vec<Arc<dyn FeatureSource>>,
+}
+
+impl CompositeSource {
+    pub fn new() -> Self {
+        CompositeSource::default()
+    }
+
+    pub fn with_source(
+        mut self,
+        source: impl FeatureSource + 'static,
+    ) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+}
+
+#[async_trait]
+impl FeatureSource for CompositeSource {
+    async fn fetch(&self) -> Result<GrowthBookResponse, GrowthbookError> {
+        let mut last_error = GrowthbookError::new(GrowthbookErrorCode::FeatureSource, "Composite source has no sources");
+        for source in &self.sources {
+            match source.fetch().await {
+                Ok(payload) => return Ok(payload),
+                Err(e) => {
+                    warn!("[growthbook-sdk] Feature source {:?} failed, trying next one: {}", source, e);
+                    last_error = e;
+                },
+            }
+        }
+        Err(last_error)
+    }
+}
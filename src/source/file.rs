@@ -0,0 +1,32 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use crate::dto::GrowthBookResponse;
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+use crate::source::FeatureSource;
+
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        FileSource { path: path.as_ref().to_path_buf() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl FeatureSource for FileSource {
+    async fn fetch(&self) -> Result<GrowthBookResponse, GrowthbookError> {
+        let contents = tokio::fs::read(&self.path)
+            .await
+            .map_err(|e| GrowthbookError::new(GrowthbookErrorCode::FeatureSource, &format!("Failed to read features file {:?}: {}", self.path, e)))?;
+        Ok(serde_json::from_slice(&contents)?)
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest_middleware::ClientWithMiddleware;
+
+use crate::dto::GrowthBookResponse;
+use crate::error::GrowthbookError;
+use crate::gateway::GrowthbookGateway;
+use crate::source::FeatureSource;
+
+#[derive(Debug, Clone)]
+pub struct HttpSource {
+    gateway: GrowthbookGateway,
+}
+
+impl HttpSource {
+    pub fn new(
+        api_url: &str,
+        sdk_key: &str,
+        timeout: Duration,
+    ) -> Result<Self, GrowthbookError> {
+        Ok(HttpSource {
+            gateway: GrowthbookGateway::new(api_url, sdk_key, timeout)?,
+        })
+    }
+
+    pub(crate) fn with_client(
+        api_url: &str,
+        sdk_key: &str,
+        client: ClientWithMiddleware,
+    ) -> Self {
+        HttpSource {
+            gateway: GrowthbookGateway::with_client(api_url, sdk_key, client),
+        }
+    }
+}
+
+#[async_trait]
+impl FeatureSource for HttpSource {
+    async fn fetch(&self) -> Result<GrowthBookResponse, GrowthbookError> {
+        self.gateway.get_features(None).await
+    }
+}
@@ -0,0 +1,16 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+
+use crate::dto::GrowthBookResponse;
+use crate::error::GrowthbookError;
+
+pub mod composite;
+pub mod file;
+pub mod http;
+pub mod static_source;
+
+#[async_trait]
+pub trait FeatureSource: Debug + Send + Sync {
+    async fn fetch(&self) -> Result<GrowthBookResponse, GrowthbookError>;
+}
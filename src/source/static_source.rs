@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::dto::GrowthBookResponse;
+use crate::error::GrowthbookError;
+use crate::source::FeatureSource;
+
+#[derive(Debug, Clone)]
+pub struct StaticSource {
+    payload: GrowthBookResponse,
+}
+
+impl StaticSource {
+    pub fn new(payload: GrowthBookResponse) -> Self {
+        StaticSource { payload }
+    }
+
+    pub fn from_value(value: Value) -> Result<Self, GrowthbookError> {
+        Ok(StaticSource::new(serde_json::from_value(value)?))
+    }
+}
+
+#[async_trait]
+impl FeatureSource for StaticSource {
+    async fn fetch(&self) -> Result<GrowthBookResponse, GrowthbookError> {
+        Ok(self.payload.clone())
+    }
+}
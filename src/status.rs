@@ -0,0 +1,401 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::{debug, error, warn};
+
+use crate::dto::GrowthBookResponse;
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+
+pub type StaleHook = Arc<dyn Fn(&ClientStatus) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailurePolicy {
+    // keeps serving the last-known-good features, even when they are stale or were recovered from a poisoned lock
+    #[default]
+    Open,
+    // evaluates every feature as off, with the `failClosed` source, while the client is not ready
+    Closed,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientStatus {
+    // false once the payload is older than the configured max staleness or its lock was poisoned
+    pub ready: bool,
+    // true when serving a cached payload or when the max staleness is exceeded
+    pub stale: bool,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<RefreshError>,
+    pub consecutive_failures: u32,
+    pub payload_age: Option<Duration>,
+    pub payload_hash: Option<String>,
+    pub date_updated: Option<DateTime<Utc>>,
+    pub feature_count: usize,
+    pub last_skipped: Option<SkippedPayload>,
+}
+
+// payload rejected because it was older than the current one
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedPayload {
+    pub date_updated: Option<DateTime<Utc>>,
+    pub skipped_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshError {
+    pub code: GrowthbookErrorCode,
+    pub message: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Staleness {
+    pub max_staleness: Option<Duration>,
+    pub on_stale: Option<StaleHook>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StatusTracker {
+    state: RwLock<TrackerState>,
+    staleness: Staleness,
+    stale_reported: AtomicBool,
+    // epoch millis up to which the client is ready, kept in sync with the state so is_ready does not lock it
+    ready_until: AtomicI64,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    last_success_at: Option<DateTime<Utc>>,
+    last_error: Option<RefreshError>,
+    consecutive_failures: u32,
+    payload_at: Option<DateTime<Utc>>,
+    payload_hash: Option<String>,
+    date_updated: Option<DateTime<Utc>>,
+    from_cache: bool,
+    poisoned: bool,
+    last_skipped: Option<SkippedPayload>,
+}
+
+impl StatusTracker {
+    pub fn new(staleness: Staleness) -> Self {
+        StatusTracker {
+            staleness,
+            ..StatusTracker::default()
+        }
+    }
+
+    pub fn record_success(
+        &self,
+        payload: &GrowthBookResponse,
+    ) {
+        let now = Utc::now();
+        self.update(|state| {
+            state.last_success_at = Some(now);
+            state.consecutive_failures = 0;
+            state.payload_at = Some(now);
+            state.payload_hash = Some(payload_hash(payload));
+            state.date_updated = payload.date_updated;
+            state.from_cache = false;
+            state.poisoned = false;
+        });
+        self.stale_reported.store(false, Ordering::Relaxed);
+    }
+
+    pub fn record_cached(
+        &self,
+        payload: &GrowthBookResponse,
+        saved_at: DateTime<Utc>,
+    ) {
+        self.update(|state| {
+            state.payload_at = Some(saved_at);
+            state.payload_hash = Some(payload_hash(payload));
+            state.date_updated = payload.date_updated;
+            state.from_cache = true;
+        });
+        let payload_age_ms = Utc::now().signed_duration_since(saved_at).num_milliseconds();
+        warn!(stale = true, payload_age_ms, "[growthbook-sdk] Serving stale payload from cache saved at {}", saved_at);
+    }
+
+    pub fn record_failure(
+        &self,
+        error: &GrowthbookError,
+    ) {
+        self.update(|state| {
+            state.last_error = Some(RefreshError {
+                code: error.code.clone(),
+                message: error.message.clone(),
+                occurred_at: Utc::now(),
+            });
+            state.consecutive_failures += 1;
+        });
+    }
+
+    pub fn record_skipped(
+        &self,
+        payload: &GrowthBookResponse,
+    ) {
+        let now = Utc::now();
+        self.update(|state| {
+            // the source answered, so the current payload is still the latest one and stays fresh
+            state.last_success_at = Some(now);
+            state.consecutive_failures = 0;
+            if state.payload_at.is_some() {
+                state.payload_at = Some(now);
+            }
+            state.last_skipped = Some(SkippedPayload {
+                date_updated: payload.date_updated,
+                skipped_at: now,
+            });
+        });
+        self.stale_reported.store(false, Ordering::Relaxed);
+    }
+
+    // cleared by the next successful refresh, which replaces the recovered features
+    pub fn record_poisoned(&self) {
+        self.update(|state| state.poisoned = true);
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.status(0).stale
+    }
+
+    pub fn status(
+        &self,
+        feature_count: usize,
+    ) -> ClientStatus {
+        let state = match self.state.read() {
+            Ok(state) => state,
+            Err(e) => e.into_inner(),
+        };
+        let payload_age = state.payload_at.and_then(|payload_at| Utc::now().signed_duration_since(payload_at).to_std().ok());
+        let expired = matches!((payload_age, self.staleness.max_staleness), (Some(age), Some(max_staleness)) if age > max_staleness);
+        ClientStatus {
+            ready: state.payload_at.is_some() && !expired && !state.poisoned,
+            stale: state.from_cache || expired,
+            last_success_at: state.last_success_at,
+            last_error: state.last_error.clone(),
+            consecutive_failures: state.consecutive_failures,
+            payload_age,
+            payload_hash: state.payload_hash.clone(),
+            date_updated: state.date_updated,
+            feature_count,
+            last_skipped: state.last_skipped.clone(),
+        }
+    }
+
+    // cheap check for the evaluation hot path, agrees with status().ready
+    pub fn is_ready(&self) -> bool {
+        Utc::now().timestamp_millis() <= self.ready_until.load(Ordering::Relaxed)
+    }
+
+    // time until the payload may go past the max staleness, None without a max staleness
+    pub fn stale_in(&self) -> Option<Duration> {
+        let max_staleness = self.staleness.max_staleness?;
+        if self.stale_reported.load(Ordering::Relaxed) {
+            // checked again after a successful refresh resets the report
+            return Some(max_staleness);
+        }
+        let payload_age = self.status(0).payload_age.unwrap_or_default();
+        Some(max_staleness.saturating_sub(payload_age) + Duration::from_millis(1))
+    }
+
+    // fires the stale hook once each time the payload goes past the max staleness
+    pub fn check_staleness(
+        &self,
+        feature_count: usize,
+    ) -> ClientStatus {
+        let status = self.status(feature_count);
+        let payload_age_ms = status.payload_age.map(|age| age.as_millis() as u64);
+        // emitted on every check so metrics layers can track staleness as a gauge
+        debug!(
+            ready = status.ready,
+            stale = status.stale,
+            payload_age_ms,
+            consecutive_failures = status.consecutive_failures,
+            "[growthbook-sdk] Payload status"
+        );
+        if !status.ready && status.payload_age.is_some() && !self.stale_reported.swap(true, Ordering::Relaxed) {
+            warn!(stale = true, payload_age_ms, "[growthbook-sdk] Payload is older than the max staleness: {:?}", status.payload_age);
+            if let Some(on_stale) = &self.staleness.on_stale {
+                on_stale(&status);
+            }
+        }
+        status
+    }
+
+    fn update(
+        &self,
+        change: impl FnOnce(&mut TrackerState),
+    ) {
+        match self.state.write() {
+            Ok(mut state) => {
+                change(&mut state);
+                self.ready_until.store(self.ready_until(&state), Ordering::Relaxed);
+            },
+            Err(e) => error!("[growthbook-sdk] problem to write status data {:?}", e),
+        }
+    }
+
+    fn ready_until(
+        &self,
+        state: &TrackerState,
+    ) -> i64 {
+        match (state.payload_at, self.staleness.max_staleness) {
+            (Some(_), _) if state.poisoned => i64::MIN,
+            (Some(payload_at), Some(max_staleness)) => payload_at.timestamp_millis().saturating_add(i64::try_from(max_staleness.as_millis()).unwrap_or(i64::MAX)),
+            (Some(_), None) => i64::MAX,
+            (None, _) => i64::MIN,
+        }
+    }
+}
+
+impl Debug for Staleness {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Staleness")
+            .field("max_staleness", &self.max_staleness)
+            .field("on_stale", &self.on_stale.is_some())
+            .finish()
+    }
+}
+
+fn payload_hash(payload: &GrowthBookResponse) -> String {
+    // features are hash maps, keys are sorted so the same payload always gets the same hash
+    let canonical = serde_json::to_value(payload).map(sorted).unwrap_or(Value::Null);
+    format!("{:x}", Sha256::digest(canonical.to_string().as_bytes()))
+}
+
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(entries.into_iter().map(|(key, value)| (key, sorted(value))).collect())
+        },
+        Value::Array(items) => Value::Array(items.into_iter().map(sorted).collect()),
+        it => it,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use chrono::{SecondsFormat, Utc};
+    use serde_json::json;
+
+    use crate::dto::GrowthBookResponse;
+    use crate::error::{GrowthbookError, GrowthbookErrorCode};
+    use crate::status::{payload_hash, Staleness, StatusTracker};
+
+    fn payload() -> GrowthBookResponse {
+        serde_json::from_value(json!({
+            "features": {
+                "a": { "defaultValue": true, "rules": [{ "condition": { "x": 1, "y": 2, "z": 3 }, "force": false }] },
+                "b": { "defaultValue": 1 },
+                "c": { "defaultValue": "c" }
+            },
+            "dateUpdated": "2024-05-29T18:43:22.153Z"
+        }))
+        .expect("Failed to create payload")
+    }
+
+    #[test]
+    fn should_hash_same_payload_the_same_way() {
+        let hashes: Vec<_> = (0..10).map(|_| payload_hash(&payload())).collect();
+
+        assert!(hashes.iter().all(|hash| *hash == hashes[0]));
+        assert_ne!(hashes[0], payload_hash(&GrowthBookResponse::default()));
+    }
+
+    #[test]
+    fn should_track_failures_until_next_success() {
+        let tracker = StatusTracker::new(Staleness::default());
+        tracker.record_success(&payload());
+        tracker.record_failure(&GrowthbookError::new(GrowthbookErrorCode::GrowthbookGateway, "boom"));
+        tracker.record_failure(&GrowthbookError::new(GrowthbookErrorCode::GrowthbookGateway, "boom"));
+
+        let status = tracker.status(3);
+        assert_eq!(2, status.consecutive_failures);
+        assert_eq!(Some(GrowthbookErrorCode::GrowthbookGateway), status.last_error.map(|it| it.code));
+        assert_eq!(
+            Some(String::from("2024-05-29T18:43:22.153Z")),
+            status.date_updated.map(|it| it.to_rfc3339_opts(SecondsFormat::Millis, true))
+        );
+        assert!(status.ready);
+
+        tracker.record_success(&payload());
+        assert_eq!(0, tracker.status(3).consecutive_failures);
+    }
+
+    #[test]
+    fn should_not_be_ready_before_first_payload_or_after_poisoning() {
+        let tracker = StatusTracker::new(Staleness::default());
+        assert!(!tracker.is_ready());
+
+        tracker.record_success(&payload());
+        assert!(tracker.is_ready());
+
+        tracker.record_poisoned();
+        assert!(!tracker.is_ready());
+    }
+
+    #[test]
+    fn should_report_stale_once_max_staleness_is_exceeded() {
+        let reported = Arc::new(AtomicUsize::new(0));
+        let counter = reported.clone();
+        let tracker = StatusTracker::new(Staleness {
+            max_staleness: Some(Duration::from_secs(60)),
+            on_stale: Some(Arc::new(move |_: &_| {
+                counter.fetch_add(1, Ordering::Relaxed);
+            })),
+        });
+        tracker.record_cached(&payload(), Utc::now() - chrono::Duration::minutes(5));
+
+        let status = tracker.check_staleness(3);
+        tracker.check_staleness(3);
+
+        assert!(!status.ready);
+        assert!(!tracker.is_ready());
+        assert!(status.stale);
+        assert_eq!(1, reported.load(Ordering::Relaxed));
+
+        tracker.record_success(&payload());
+        let status = tracker.check_staleness(3);
+        assert!(status.ready);
+        assert!(tracker.is_ready());
+        assert!(!status.stale);
+    }
+
+    #[test]
+    fn should_keep_payload_fresh_when_older_payload_is_skipped() {
+        let tracker = StatusTracker::new(Staleness {
+            max_staleness: Some(Duration::from_secs(60)),
+            on_stale: None,
+        });
+        tracker.record_success(&payload());
+        tracker.update(|state| state.payload_at = Some(Utc::now() - chrono::Duration::minutes(5)));
+        assert!(!tracker.status(3).ready);
+
+        tracker.record_skipped(&GrowthBookResponse::default());
+
+        let status = tracker.status(3);
+        assert!(status.ready);
+        assert!(tracker.is_ready());
+        assert!(!status.stale);
+        assert!(status.payload_age.is_some_and(|age| age < Duration::from_secs(60)));
+        assert!(status.last_skipped.is_some());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod model;
+pub(crate) mod notifier;
+mod use_case;
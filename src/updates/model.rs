@@ -0,0 +1,55 @@
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadUpdate {
+    pub version: u64,
+    pub diff: PayloadDiff,
+}
+
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<FeatureChange>,
+    // experiment keys whose forced variation was added, removed or changed
+    pub forced_variations: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureChange {
+    pub feature_id: String,
+    pub default_value_changed: bool,
+    pub rules: Vec<RuleChange>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleChange {
+    pub index: usize,
+    pub id: Option<String>,
+    pub kind: RuleChangeKind,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl PayloadDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty() && self.forced_variations.is_empty()
+    }
+
+    pub fn contains(
+        &self,
+        feature_id: &str,
+    ) -> bool {
+        self.added.iter().any(|it| it == feature_id) || self.removed.iter().any(|it| it == feature_id) || self.changed.iter().any(|it| it.feature_id == feature_id)
+    }
+}
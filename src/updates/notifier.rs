@@ -0,0 +1,41 @@
+use tokio::sync::{broadcast, watch};
+
+use crate::updates::model::{PayloadDiff, PayloadUpdate};
+
+const UPDATES_CAPACITY: usize = 16;
+
+#[derive(Debug)]
+pub struct PayloadNotifier {
+    version: watch::Sender<u64>,
+    updates: broadcast::Sender<PayloadUpdate>,
+}
+
+impl PayloadNotifier {
+    pub fn new() -> Self {
+        PayloadNotifier {
+            version: watch::channel(0).0,
+            updates: broadcast::channel(UPDATES_CAPACITY).0,
+        }
+    }
+
+    pub fn notify(
+        &self,
+        diff: PayloadDiff,
+    ) {
+        if diff.is_empty() {
+            return;
+        }
+        self.version.send_modify(|version| *version += 1);
+        let version = *self.version.borrow();
+        // Sending only fails when there are no subscribers.
+        let _ = self.updates.send(PayloadUpdate { version, diff });
+    }
+
+    pub fn watch_version(&self) -> watch::Receiver<u64> {
+        self.version.subscribe()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<PayloadUpdate> {
+        self.updates.subscribe()
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use crate::dto::{GrowthBookFeature, GrowthBookFeatureRule};
+use crate::updates::model::{FeatureChange, PayloadDiff, RuleChange, RuleChangeKind};
+
+impl PayloadDiff {
+    pub fn between(
+        previous: &HashMap<String, GrowthBookFeature>,
+        current: &HashMap<String, GrowthBookFeature>,
+    ) -> Self {
+        let mut added: Vec<String> = current.keys().filter(|key| !previous.contains_key(*key)).cloned().collect();
+        let mut removed: Vec<String> = previous.keys().filter(|key| !current.contains_key(*key)).cloned().collect();
+        let mut changed: Vec<FeatureChange> = current
+            .iter()
+            .filter_map(|(key, feature)| previous.get(key).and_then(|previous_feature| feature_change(key, previous_feature, feature)))
+            .collect();
+        added.sort();
+        removed.sort();
+        changed.sort_by(|a, b| a.feature_id.cmp(&b.feature_id));
+        PayloadDiff {
+            added,
+            removed,
+            changed,
+            forced_variations: vec![],
+        }
+    }
+
+    pub fn with_forced_variations(
+        mut self,
+        previous: &Option<HashMap<String, i64>>,
+        current: &Option<HashMap<String, i64>>,
+    ) -> Self {
+        let empty = HashMap::new();
+        let previous = previous.as_ref().unwrap_or(&empty);
+        let current = current.as_ref().unwrap_or(&empty);
+        let mut keys: Vec<String> = previous.keys().chain(current.keys()).filter(|key| previous.get(*key) != current.get(*key)).cloned().collect();
+        keys.sort();
+        keys.dedup();
+        self.forced_variations = keys;
+        self
+    }
+}
+
+fn feature_change(
+    feature_id: &str,
+    previous: &GrowthBookFeature,
+    current: &GrowthBookFeature,
+) -> Option<FeatureChange> {
+    if previous == current {
+        return None;
+    }
+    let previous_rules = previous.rules.as_deref().unwrap_or_default();
+    let current_rules = current.rules.as_deref().unwrap_or_default();
+    let mut rules = vec![];
+    for (index, rule) in current_rules.iter().enumerate() {
+        match counterpart(rule, index, previous_rules) {
+            None => rules.push(RuleChange {
+                index,
+                id: rule.id(),
+                kind: RuleChangeKind::Added,
+            }),
+            Some(previous) if previous != rule => rules.push(RuleChange {
+                index,
+                id: rule.id(),
+                kind: RuleChangeKind::Changed,
+            }),
+            _ => {},
+        }
+    }
+    for (index, rule) in previous_rules.iter().enumerate() {
+        if counterpart(rule, index, current_rules).is_none() {
+            rules.push(RuleChange {
+                index,
+                id: rule.id(),
+                kind: RuleChangeKind::Removed,
+            });
+        }
+    }
+    rules.sort_by_key(|it| it.index);
+    Some(FeatureChange {
+        feature_id: String::from(feature_id),
+        default_value_changed: previous.default_value != current.default_value,
+        rules,
+    })
+}
+
+// rules with an id are matched by id wherever they moved, the others by position
+fn counterpart<'a>(
+    rule: &GrowthBookFeatureRule,
+    index: usize,
+    others: &'a [GrowthBookFeatureRule],
+) -> Option<&'a GrowthBookFeatureRule> {
+    match rule.id() {
+        Some(id) => others.iter().find(|other| other.id().as_ref() == Some(&id)),
+        None => others.get(index).filter(|other| other.id().is_none()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use crate::dto::GrowthBookFeature;
+    use crate::updates::model::{FeatureChange, PayloadDiff, RuleChange, RuleChangeKind};
+
+    fn features(value: serde_json::Value) -> HashMap<String, GrowthBookFeature> {
+        serde_json::from_value(value).expect("Failed to create features")
+    }
+
+    #[test]
+    fn diff_features_and_rules() {
+        let previous = features(json!({
+            "kept": { "defaultValue": true },
+            "removed": { "defaultValue": true },
+            "changed": {
+                "defaultValue": 1,
+                "rules": [
+                    { "id": "fr_1", "force": 1, "condition": { "country": "BR" } },
+                    { "id": "fr_2", "force": 2 },
+                    { "id": "fr_3", "force": 3 }
+                ]
+            }
+        }));
+        let current = features(json!({
+            "kept": { "defaultValue": true },
+            "added": { "defaultValue": false },
+            "changed": {
+                "defaultValue": 1,
+                "rules": [
+                    { "id": "fr_1", "force": 1, "condition": { "country": "US" } },
+                    { "id": "fr_2", "force": 2 }
+                ]
+            }
+        }));
+
+        let diff = PayloadDiff::between(&previous, &current);
+
+        assert_eq!(
+            PayloadDiff {
+                added: vec![String::from("added")],
+                removed: vec![String::from("removed")],
+                changed: vec![FeatureChange {
+                    feature_id: String::from("changed"),
+                    default_value_changed: false,
+                    rules: vec![
+                        RuleChange {
+                            index: 0,
+                            id: Some(String::from("fr_1")),
+                            kind: RuleChangeKind::Changed,
+                        },
+                        RuleChange {
+                            index: 2,
+                            id: Some(String::from("fr_3")),
+                            kind: RuleChangeKind::Removed,
+                        },
+                    ],
+                }],
+                forced_variations: vec![],
+            },
+            diff
+        );
+    }
+
+    #[test]
+    fn match_rules_by_id_when_inserted_before_them() {
+        let previous = features(json!({
+            "flag": { "defaultValue": 1, "rules": [{ "id": "fr_1", "force": 1 }, { "id": "fr_2", "force": 2 }] }
+        }));
+        let current = features(json!({
+            "flag": { "defaultValue": 1, "rules": [{ "id": "fr_0", "force": 0 }, { "id": "fr_1", "force": 1 }, { "id": "fr_2", "force": 3 }] }
+        }));
+
+        let diff = PayloadDiff::between(&previous, &current);
+
+        assert_eq!(
+            vec![
+                RuleChange {
+                    index: 0,
+                    id: Some(String::from("fr_0")),
+                    kind: RuleChangeKind::Added,
+                },
+                RuleChange {
+                    index: 2,
+                    id: Some(String::from("fr_2")),
+                    kind: RuleChangeKind::Changed,
+                },
+            ],
+            diff.changed[0].rules
+        );
+    }
+
+    #[test]
+    fn diff_forced_variations() {
+        let previous = features(json!({ "flag": { "defaultValue": true } }));
+        let diff = PayloadDiff::between(&previous, &previous.clone()).with_forced_variations(
+            &Some(HashMap::from([(String::from("exp-1"), 0), (String::from("exp-2"), 1)])),
+            &Some(HashMap::from([(String::from("exp-2"), 0), (String::from("exp-3"), 1)])),
+        );
+
+        assert!(!diff.is_empty());
+        assert_eq!(vec![String::from("exp-1"), String::from("exp-2"), String::from("exp-3")], diff.forced_variations);
+        assert!(PayloadDiff::between(&previous, &previous.clone()).with_forced_variations(&None, &Some(HashMap::new())).is_empty());
+    }
+
+    #[test]
+    fn empty_diff_for_same_features() {
+        let previous = features(json!({ "flag": { "defaultValue": true, "rules": [{ "force": false }] } }));
+
+        assert!(PayloadDiff::between(&previous, &previous.clone()).is_empty());
+    }
+}
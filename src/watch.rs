@@ -0,0 +1,168 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+
+use futures_util::stream::{self, BoxStream};
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::sync::oneshot;
+use tokio::sync::watch::Receiver;
+
+use crate::client::{GrowthBookClient, GrowthBookClientTrait};
+use crate::model_public::{FeatureResult, GrowthBookAttribute};
+use crate::options::EvaluationDiagnostic;
+
+pub struct ConfigHandle<T> {
+    state: Arc<RwLock<ConfigState<T>>>,
+    // dropped with the last handle, which stops the update task
+    _stop: Arc<oneshot::Sender<()>>,
+}
+
+struct ConfigState<T> {
+    value: Arc<T>,
+    raw_value: Option<Value>,
+    last_error: Option<String>,
+}
+
+impl<T> ConfigHandle<T>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    pub(crate) fn spawn(
+        client: GrowthBookClient,
+        mut updates: Receiver<u64>,
+        feature_name: &str,
+        user_attributes: Option<Vec<GrowthBookAttribute>>,
+        default: T,
+    ) -> Self {
+        let feature_name = String::from(feature_name);
+        let state = Arc::new(RwLock::new(ConfigState {
+            value: Arc::new(default),
+            raw_value: None,
+            last_error: None,
+        }));
+        refresh(&client, &feature_name, &user_attributes, &state);
+
+        let (stop, mut stopped) = oneshot::channel();
+        let task_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut stopped => break,
+                    changed = updates.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                        refresh(&client, &feature_name, &user_attributes, &task_state);
+                    },
+                }
+            }
+        });
+
+        ConfigHandle { state, _stop: Arc::new(stop) }
+    }
+}
+
+impl<T> ConfigHandle<T> {
+    pub fn get(&self) -> Arc<T> {
+        match self.state.read() {
+            Ok(state) => Arc::clone(&state.value),
+            Err(poisoned) => Arc::clone(&poisoned.into_inner().value),
+        }
+    }
+
+    pub fn last_error(&self) -> Option<String> {
+        match self.state.read() {
+            Ok(state) => state.last_error.clone(),
+            Err(poisoned) => poisoned.into_inner().last_error.clone(),
+        }
+    }
+}
+
+impl<T> Clone for ConfigHandle<T> {
+    fn clone(&self) -> Self {
+        ConfigHandle {
+            state: Arc::clone(&self.state),
+            _stop: Arc::clone(&self._stop),
+        }
+    }
+}
+
+impl<T: Debug> Debug for ConfigHandle<T> {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("ConfigHandle").field("value", &self.get()).field("last_error", &self.last_error()).finish()
+    }
+}
+
+fn refresh<T>(
+    client: &GrowthBookClient,
+    feature_name: &str,
+    user_attributes: &Option<Vec<GrowthBookAttribute>>,
+    state: &RwLock<ConfigState<T>>,
+) where
+    T: DeserializeOwned,
+{
+    let raw_value = client.feature_result(feature_name, user_attributes.clone()).value;
+    if state.read().is_ok_and(|it| it.raw_value.as_ref() == Some(&raw_value)) {
+        return;
+    }
+
+    let result = serde_json::from_value::<T>(raw_value.clone());
+    let Ok(mut state) = state.write() else {
+        return;
+    };
+    state.raw_value = Some(raw_value);
+    match result {
+        Ok(value) => {
+            state.value = Arc::new(value);
+            state.last_error = None;
+        },
+        Err(error) => {
+            let message = error.to_string();
+            state.last_error = Some(message.clone());
+            client.report(EvaluationDiagnostic::ConfigDeserializationFailed {
+                feature: String::from(feature_name),
+                message,
+            });
+        },
+    }
+}
+
+struct FeatureWatch {
+    client: GrowthBookClient,
+    updates: Receiver<u64>,
+    feature_name: String,
+    user_attributes: Option<Vec<GrowthBookAttribute>>,
+    last_value: Option<Value>,
+}
+
+pub(crate) fn feature_stream(
+    client: GrowthBookClient,
+    updates: Receiver<u64>,
+    feature_name: &str,
+    user_attributes: Option<Vec<GrowthBookAttribute>>,
+) -> BoxStream<'static, FeatureResult> {
+    let watch = FeatureWatch {
+        client,
+        updates,
+        feature_name: String::from(feature_name),
+        user_attributes,
+        last_value: None,
+    };
+    stream::unfold(watch, |mut watch| async move {
+        loop {
+            if watch.last_value.is_some() {
+                watch.updates.changed().await.ok()?;
+            }
+            let result = watch.client.feature_result(&watch.feature_name, watch.user_attributes.clone());
+            if watch.last_value.as_ref() != Some(&result.value) {
+                watch.last_value = Some(result.value.clone());
+                return Some((result, watch));
+            }
+        }
+    })
+    .boxed()
+}
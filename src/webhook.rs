@@ -0,0 +1,56 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::{info, warn};
+
+use crate::client::GrowthBookClient;
+use crate::error::{GrowthbookError, GrowthbookErrorCode};
+
+pub const SIGNATURE_HEADER: &str = "X-GrowthBook-Signature";
+
+#[derive(Debug, Clone)]
+pub struct WebhookHandler {
+    client: GrowthBookClient,
+    secret: String,
+}
+
+impl WebhookHandler {
+    pub fn new(
+        client: GrowthBookClient,
+        secret: &str,
+    ) -> Self {
+        WebhookHandler { client, secret: String::from(secret) }
+    }
+
+    // `signature` is the value of the `X-GrowthBook-Signature` header and `body` the raw request body
+    pub fn verify(
+        &self,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> Result<(), GrowthbookError> {
+        let signature = signature.ok_or_else(|| invalid_signature("Missing webhook signature"))?;
+        let signature = signature.trim();
+        let signature = hex::decode(signature.strip_prefix("sha256=").unwrap_or(signature)).map_err(|_| invalid_signature("Webhook signature is not hex encoded"))?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes()).map_err(|e| invalid_signature(&e.to_string()))?;
+        mac.update(body);
+        mac.verify_slice(&signature).map_err(|_| invalid_signature("Webhook signature does not match"))
+    }
+
+    // verifies the request and refreshes the client, returning whether the features changed
+    pub async fn handle(
+        &self,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> Result<bool, GrowthbookError> {
+        if let Err(e) = self.verify(signature, body) {
+            warn!("[growthbook-sdk] Rejected webhook: {}", e);
+            return Err(e);
+        }
+        let changed = self.client.refresh_now().await?;
+        info!("[growthbook-sdk] Refreshed features from webhook, changed={}", changed);
+        Ok(changed)
+    }
+}
+
+fn invalid_signature(message: &str) -> GrowthbookError {
+    GrowthbookError::new(GrowthbookErrorCode::InvalidWebhookSignature, message)
+}
@@ -0,0 +1,134 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClientTrait;
+    use growthbook_rust_sdk::registry::ClientRegistry;
+    use serde_json::json;
+    use uuid::Uuid;
+    use wiremock::ResponseTemplate;
+
+    use crate::commons::{eventually, mount_payload, PayloadServer};
+
+    fn features(
+        flag: &str,
+        enabled: bool,
+    ) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({ "features": { flag: { "defaultValue": enabled } } }))
+    }
+
+    #[tokio::test]
+    async fn should_create_clients_lazily_per_sdk_key() -> Result<(), Box<dyn std::error::Error>> {
+        let server = PayloadServer::start().await;
+        let (first_key, second_key) = (server.sdk_key, Uuid::now_v7());
+        mount_payload(&server.mock_server, first_key, features("first-project-flag", true), None).await;
+        mount_payload(&server.mock_server, second_key, features("second-project-flag", true), None).await;
+        let registry = ClientRegistry::builder().update_interval(Duration::from_secs(600)).build()?;
+
+        assert!(registry.is_empty());
+        assert_eq!(0, server.requests().await);
+
+        let first = registry.client(&server.uri(), &first_key.to_string()).await?;
+        let first_again = registry.client(&server.uri(), &first_key.to_string()).await?;
+        let second = registry.client(&server.uri(), &second_key.to_string()).await?;
+
+        assert!(Arc::ptr_eq(&first.gb, &first_again.gb));
+        assert!(first.is_on("first-project-flag", None));
+        assert!(second.is_on("second-project-flag", None));
+        assert!(second.is_off("first-project-flag", None));
+        assert_eq!(2, registry.len());
+        assert_eq!(2, server.requests().await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refresh_clients_independently() -> Result<(), Box<dyn std::error::Error>> {
+        let server = PayloadServer::start().await;
+        let (first_key, failing_key) = (server.sdk_key, Uuid::now_v7());
+        mount_payload(&server.mock_server, first_key, features("registry-flag", false), Some(2)).await;
+        mount_payload(&server.mock_server, first_key, features("registry-flag", true), None).await;
+        mount_payload(&server.mock_server, failing_key, features("registry-flag", false), Some(1)).await;
+        mount_payload(&server.mock_server, failing_key, ResponseTemplate::new(503), None).await;
+        let registry = ClientRegistry::builder().update_interval(Duration::from_millis(100)).build()?;
+
+        let first = registry.client(&server.uri(), &first_key.to_string()).await?;
+        let failing = registry.client(&server.uri(), &failing_key.to_string()).await?;
+        assert!(first.is_off("registry-flag", None));
+
+        // the failing tenant backs off for seconds, the other one keeps its own 100ms schedule
+        assert!(eventually(Duration::from_secs(1), || first.is_on("registry-flag", None)).await);
+        assert!(failing.is_off("registry-flag", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_evict_idle_clients_once_no_longer_held() -> Result<(), Box<dyn std::error::Error>> {
+        let server = PayloadServer::start().await;
+        let sdk_key = server.sdk_key;
+        mount_payload(&server.mock_server, sdk_key, features("idle-flag", true), None).await;
+        let registry = ClientRegistry::builder().update_interval(Duration::from_millis(100)).idle_timeout(Duration::from_millis(200)).build()?;
+
+        let client = registry.client(&server.uri(), &sdk_key.to_string()).await?;
+        let gb = Arc::clone(&client.gb);
+
+        // held clients keep being refreshed past the idle timeout
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert_eq!(1, registry.len());
+        assert!(server.requests().await > 2);
+        assert!(client.is_on("idle-flag", None));
+
+        drop(client);
+        assert!(eventually(Duration::from_secs(2), || registry.is_empty()).await);
+        let refreshes = server.requests().await;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(refreshes, server.requests().await);
+
+        let recreated = registry.client(&server.uri(), &sdk_key.to_string()).await?;
+        assert!(!Arc::ptr_eq(&gb, &recreated.gb));
+        assert_eq!(1, registry.len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refresh_removed_clients_until_last_handle_drops() -> Result<(), Box<dyn std::error::Error>> {
+        let server = PayloadServer::start().await;
+        let sdk_key = server.sdk_key;
+        mount_payload(&server.mock_server, sdk_key, features("removed-flag", false), Some(1)).await;
+        mount_payload(&server.mock_server, sdk_key, features("removed-flag", true), None).await;
+        let registry = ClientRegistry::builder().update_interval(Duration::from_millis(100)).build()?;
+
+        let client = registry.client(&server.uri(), &sdk_key.to_string()).await?;
+        assert!(registry.remove(&server.uri(), &sdk_key.to_string()));
+        assert!(registry.is_empty());
+
+        assert!(eventually(Duration::from_secs(1), || client.is_on("removed-flag", None)).await);
+
+        drop(client);
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let refreshes = server.requests().await;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(refreshes, server.requests().await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_keep_connections_alive() -> Result<(), Box<dyn std::error::Error>> {
+        let server = PayloadServer::with_payload(json!({ "features": {} })).await;
+        let registry = ClientRegistry::builder().update_interval(Duration::from_secs(600)).build()?;
+
+        registry.client(&server.uri(), &server.sdk_key.to_string()).await?;
+
+        let requests = server.mock_server.received_requests().await.unwrap_or_default();
+        assert_eq!(1, requests.len());
+        assert!(requests.iter().all(|request| request.headers.get("connection").is_none_or(|value| value != "close")));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use chrono::{DateTime, Utc};
+    use growthbook_rust_sdk::error::GrowthbookErrorCode;
+    use growthbook_rust_sdk::refresh::model::RetryPolicy;
+    use serde_json::json;
+    use wiremock::ResponseTemplate;
+
+    use crate::commons::PayloadServer;
+
+    async fn flaky_server() -> PayloadServer {
+        let server = PayloadServer::start().await;
+        let payload = json!({
+            "features": {
+                "first-flag": { "defaultValue": true },
+                "second-flag": { "defaultValue": false }
+            },
+            "dateUpdated": "2024-05-29T18:43:22.153Z"
+        });
+        server.respond(ResponseTemplate::new(200).set_body_json(payload), Some(1)).await;
+        server.respond(ResponseTemplate::new(503), None).await;
+        server
+    }
+
+    #[tokio::test]
+    async fn should_report_status_of_last_refreshes() -> Result<(), Box<dyn std::error::Error>> {
+        let server = flaky_server().await;
+
+        let client = server.builder().update_interval(Duration::from_millis(100)).retry_policy(RetryPolicy::no_retry()).build().await?;
+
+        let status = client.status();
+        assert!(status.ready);
+        assert!(!status.stale);
+        assert!(status.last_success_at.is_some());
+        assert_eq!(2, status.feature_count);
+        assert_eq!(Some("2024-05-29T18:43:22.153Z".parse::<DateTime<Utc>>()?), status.date_updated);
+        assert_eq!(Some(64), status.payload_hash.as_ref().map(String::len));
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let status = client.status();
+        assert!(status.ready);
+        assert!(status.consecutive_failures >= 2);
+        assert_eq!(Some(GrowthbookErrorCode::GrowthbookGateway), status.last_error.map(|error| error.code));
+        assert!(status.payload_age >= Some(Duration::from_millis(500)));
+        assert_eq!(2, status.feature_count);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_flag_stale_payload_after_max_staleness() -> Result<(), Box<dyn std::error::Error>> {
+        let server = flaky_server().await;
+        let stale_reports = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&stale_reports);
+
+        let client = server
+            .builder()
+            .update_interval(Duration::from_millis(100))
+            .retry_policy(RetryPolicy::no_retry())
+            .max_staleness(Duration::from_millis(300))
+            .on_stale(move |status| {
+                assert!(!status.ready);
+                counter.fetch_add(1, Ordering::Relaxed);
+            })
+            .build()
+            .await?;
+        assert!(!client.is_stale());
+
+        tokio::time::sleep(Duration::from_millis(800)).await;
+
+        let status = client.status();
+        assert!(!status.ready);
+        assert!(status.stale);
+        assert!(client.is_stale());
+        assert_eq!(1, stale_reports.load(Ordering::Relaxed));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fire_stale_hook_between_refreshes_without_status_calls() -> Result<(), Box<dyn std::error::Error>> {
+        let server = flaky_server().await;
+        let stale_reports = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&stale_reports);
+
+        let client = server
+            .builder()
+            .update_interval(Duration::from_secs(60))
+            .max_staleness(Duration::from_millis(300))
+            .on_stale(move |_| {
+                counter.fetch_add(1, Ordering::Relaxed);
+            })
+            .build()
+            .await?;
+        assert!(client.status().ready);
+
+        tokio::time::sleep(Duration::from_millis(800)).await;
+
+        assert_eq!(1, stale_reports.load(Ordering::Relaxed));
+        assert!(!client.status().ready);
+        assert_eq!(1, stale_reports.load(Ordering::Relaxed));
+
+        Ok(())
+    }
+}
@@ -1,10 +1,18 @@
+use std::collections::VecDeque;
 use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use growthbook_rust_sdk::client::GrowthBookClient;
+use async_trait::async_trait;
+use growthbook_rust_sdk::client::{GrowthBookClient, GrowthBookClientBuilder};
+use growthbook_rust_sdk::dto::GrowthBookResponse;
+use growthbook_rust_sdk::error::{GrowthbookError, GrowthbookErrorCode};
+use growthbook_rust_sdk::source::FeatureSource;
 use rand::Rng;
 use reqwest::StatusCode;
 use serde_json::{json, Value};
 use test_context::AsyncTestContext;
+use tokio::time::Instant;
 use uuid::Uuid;
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -15,6 +23,149 @@ pub struct TestContext {
     pub growthbook: GrowthBookClient,
 }
 
+// mock GrowthBook API serving the payload of a random sdk key
+#[allow(dead_code)]
+pub struct PayloadServer {
+    pub mock_server: MockServer,
+    pub sdk_key: Uuid,
+}
+
+#[allow(dead_code)]
+impl PayloadServer {
+    pub async fn start() -> Self {
+        PayloadServer {
+            mock_server: MockServer::start().await,
+            sdk_key: Uuid::now_v7(),
+        }
+    }
+
+    pub async fn with_payload(body: Value) -> Self {
+        let server = PayloadServer::start().await;
+        server.respond(ResponseTemplate::new(200).set_body_json(body), None).await;
+        server
+    }
+
+    // mocks are matched in mount order, so a limited response is served before the ones mounted after it
+    pub async fn respond(
+        &self,
+        response: ResponseTemplate,
+        times: Option<u64>,
+    ) {
+        mount_payload(&self.mock_server, self.sdk_key, response, times).await
+    }
+
+    pub fn uri(&self) -> String {
+        self.mock_server.uri()
+    }
+
+    pub fn builder(&self) -> GrowthBookClientBuilder {
+        GrowthBookClient::builder(&self.mock_server.uri(), self.sdk_key.to_string().as_str())
+    }
+
+    pub async fn requests(&self) -> usize {
+        self.mock_server.received_requests().await.map(|it| it.len()).unwrap_or(0)
+    }
+}
+
+// an error step fails as an unavailable gateway, asking to retry after the given delay
+type Step = Result<Value, Option<Duration>>;
+
+// in-memory source serving its steps in order and the last one from then on, usable with paused time
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct SequenceSource {
+    steps: Arc<Mutex<VecDeque<Step>>>,
+    calls: Arc<Mutex<Vec<Instant>>>,
+}
+
+#[allow(dead_code)]
+impl SequenceSource {
+    pub fn new(payloads: Vec<Value>) -> Self {
+        Self::with_steps(payloads.into_iter().map(Ok).collect())
+    }
+
+    // the first fetch succeeds, then the failures are served and every fetch after them succeeds
+    pub fn failing(failures: Vec<Option<Duration>>) -> Self {
+        let empty = json!({ "features": {} });
+        let mut steps = vec![Ok(empty.clone())];
+        steps.extend(failures.into_iter().map(Err));
+        steps.push(Ok(empty));
+        Self::with_steps(steps)
+    }
+
+    fn with_steps(steps: Vec<Step>) -> Self {
+        SequenceSource {
+            steps: Arc::new(Mutex::new(steps.into())),
+            calls: Arc::default(),
+        }
+    }
+
+    // replaces the remaining steps, so every fetch from now on serves the payload
+    pub fn set(
+        &self,
+        payload: Value,
+    ) {
+        *self.steps.lock().expect("Failed to lock") = VecDeque::from([Ok(payload)]);
+    }
+
+    pub fn elapsed_seconds(
+        &self,
+        start: Instant,
+    ) -> Vec<u64> {
+        self.calls.lock().expect("Failed to lock").iter().map(|call| call.duration_since(start).as_secs()).collect()
+    }
+}
+
+#[async_trait]
+impl FeatureSource for SequenceSource {
+    async fn fetch(&self) -> Result<GrowthBookResponse, GrowthbookError> {
+        self.calls.lock().expect("Failed to lock").push(Instant::now());
+        let step = {
+            let mut steps = self.steps.lock().expect("Failed to lock");
+            if steps.len() > 1 {
+                steps.pop_front()
+            } else {
+                steps.front().cloned()
+            }
+        };
+        match step {
+            Some(Ok(payload)) => Ok(serde_json::from_value(payload)?),
+            Some(Err(retry_after)) => Err(GrowthbookError::new(GrowthbookErrorCode::GrowthbookGateway, "unavailable").with_retry_after(retry_after)),
+            None => Ok(GrowthBookResponse::default()),
+        }
+    }
+}
+
+// polls the condition until it holds or the timeout elapses
+#[allow(dead_code)]
+pub async fn eventually(
+    timeout: Duration,
+    condition: impl Fn() -> bool,
+) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    condition()
+}
+
+#[allow(dead_code)]
+pub async fn mount_payload(
+    mock_server: &MockServer,
+    sdk_key: Uuid,
+    response: ResponseTemplate,
+    times: Option<u64>,
+) {
+    let mock = Mock::given(method("GET")).and(path(format!("/api/features/{sdk_key}"))).respond_with(response);
+    match times {
+        Some(times) => mock.up_to_n_times(times).mount(mock_server).await,
+        None => mock.mount(mock_server).await,
+    }
+}
+
 impl AsyncTestContext for TestContext {
     async fn setup() -> TestContext {
         let mock_server = create_mock_server().await;
@@ -30,6 +181,7 @@ impl AsyncTestContext for TestContext {
     }
 }
 
+#[allow(dead_code)]
 pub async fn all_cases(
     mock_server: &MockServer,
     sdk: Uuid,
@@ -303,6 +455,75 @@ pub async fn all_cases(
                         "hashAttribute": "any-id"
                     }
                 ]
+            },
+            "date-time-gt-rule": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "signup_date": {
+                                "$gt": "2024-01-01T00:00:00-03:00"
+                            }
+                        },
+                        "force": true
+                    }
+                ]
+            },
+            "date-time-lt-epoch-rule": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "subscription_expires_at": {
+                                "$lt": 1735689600
+                            }
+                        },
+                        "force": true
+                    }
+                ]
+            },
+            "date-time-lt-epoch-millis-rule": {
+                "defaultValue": false,
+                "rules": [
+                    {
+                        "condition": {
+                            "subscription_expires_at": {
+                                "$lt": 1735689600000i64
+                            }
+                        },
+                        "force": true
+                    }
+                ]
+            },
+            "multiple-rules-flag": {
+                "defaultValue": "default",
+                "rules": [
+                    {
+                        "id": "fr_country",
+                        "condition": {
+                            "country": "BR"
+                        },
+                        "force": "country"
+                    },
+                    {
+                        "id": "fr_rollout",
+                        "condition": {
+                            "plan": "pro"
+                        },
+                        "force": "rollout",
+                        "coverage": 1.0,
+                        "hashAttribute": "any-id"
+                    },
+                    {
+                        "id": "fr_experiment",
+                        "coverage": 1.0,
+                        "hashAttribute": "any-id",
+                        "seed": "any-seed",
+                        "hashVersion": 2,
+                        "variations": ["control", "treatment"],
+                        "key": "multiple-rules-experiment"
+                    }
+                ]
             }
         },
         "dateUpdated": "2024-05-29T18:43:22.153Z"
@@ -310,6 +531,7 @@ pub async fn all_cases(
     mock(mock_server, sdk, body, 1, StatusCode::OK).await;
 }
 
+#[allow(dead_code)]
 async fn mock(
     mock_server: &MockServer,
     sdk: Uuid,
@@ -331,10 +553,11 @@ async fn mock(
         .await;
 }
 
+#[allow(dead_code)]
 pub async fn create_mock_server() -> MockServer {
     for _ in 1..10 {
         // try to start mock server in a random port 10 times
-        let port = rand::thread_rng().gen_range(51000..54000);
+        let port = rand::rng().random_range(51000..54000);
         let addr = SocketAddr::from(([0, 0, 0, 0], port));
         if let Ok(listener) = TcpListener::bind(addr) {
             let mock_server = MockServer::builder().listener(listener).start().await;
@@ -0,0 +1,93 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::explain::model::{RuleDecision, RuleKind};
+    use growthbook_rust_sdk::model_public::GrowthBookAttribute;
+    use rstest::rstest;
+    use serde_json::json;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_explain_unknown_feature(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let explanation = ctx.growthbook.explain("flag-not-exist", None);
+
+        assert!(!explanation.found);
+        assert!(explanation.rules.is_empty());
+        assert_eq!("unknownFeature", explanation.result.source);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_explain_failed_sub_condition(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+            "any-key": "any-other-key",
+        }))
+        .expect("Failed to create attributes");
+
+        let explanation = ctx.growthbook.explain("flag", Some(vec));
+
+        assert!(explanation.found);
+        assert_eq!(1, explanation.rules.len());
+        let rule = &explanation.rules[0];
+        assert_eq!(RuleKind::Force, rule.kind);
+        assert_eq!(RuleDecision::ConditionNotMet, rule.decision);
+        let condition = rule.condition.as_ref().expect("Failed to get condition");
+        assert!(!condition.matched);
+        assert_eq!(Some(json!({ "any-key": "018fcf64-1827-709a-a8ae-7d206aafb5e2" })), condition.failed_condition);
+        assert_eq!("defaultValue", explanation.result.source);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_explain_experiment_bucket(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5e-5b0e-75bf-92a3-7658d932634d",
+        }))
+        .expect("Failed to create attributes");
+
+        let explanation = ctx.growthbook.explain("experiment-rule-condition-ninety-coverage-flag", Some(vec));
+
+        let rule = &explanation.rules[0];
+        assert_eq!(RuleKind::Experiment, rule.kind);
+        assert_eq!(RuleDecision::Applied, rule.decision);
+        assert_eq!(Some(String::from("any-id")), rule.hash_attribute);
+        assert_eq!(Some(true), rule.in_coverage);
+        assert_eq!(Some(2), rule.variation_id);
+        assert_eq!(explanation.result.experiment_result.as_ref().and_then(|it| it.bucket), rule.bucket);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_serialize_explanation_to_json(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "018fd040-de77-72c7-af6e-6a67d430c0e6",
+        }))
+        .expect("Failed to create attributes");
+
+        let explanation = ctx.growthbook.explain("rollout-zero-percentage-flag-condition-by-attribute", Some(vec));
+        let json = serde_json::to_value(&explanation)?;
+
+        assert_eq!(json!("rollout-zero-percentage-flag-condition-by-attribute"), json["featureId"]);
+        assert_eq!(json!("rollout"), json["rules"][0]["kind"]);
+        assert_eq!(json!("notInCoverage"), json["rules"][0]["decision"]);
+        assert_eq!(json!(false), json["rules"][0]["inCoverage"]);
+        assert_eq!(json!("defaultValue"), json["result"]["source"]);
+
+        Ok(())
+    }
+}
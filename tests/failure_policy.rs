@@ -0,0 +1,102 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::{GrowthBookClient, GrowthBookClientTrait};
+    use growthbook_rust_sdk::refresh::model::RetryPolicy;
+    use growthbook_rust_sdk::source::static_source::StaticSource;
+    use growthbook_rust_sdk::status::FailurePolicy;
+    use serde_json::json;
+    use wiremock::ResponseTemplate;
+
+    use crate::commons::PayloadServer;
+
+    fn source() -> StaticSource {
+        StaticSource::from_value(json!({ "features": { "guarded-flag": { "defaultValue": true } } })).expect("Failed to create source")
+    }
+
+    fn poison(client: &GrowthBookClient) {
+        let gb = client.gb.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = gb.write().unwrap();
+            panic!("poisoning the gb data lock");
+        })
+        .join();
+        assert!(client.gb.is_poisoned());
+    }
+
+    #[tokio::test]
+    async fn should_keep_last_known_good_features_when_lock_is_poisoned() -> Result<(), Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::from_source(source()).update_interval(Duration::from_secs(600)).build().await?;
+
+        poison(&client);
+
+        assert!(client.is_on("guarded-flag", None));
+        assert_eq!(1, client.total_features());
+        assert!(!client.gb.is_poisoned());
+        assert!(!client.status().ready);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_closed_until_poisoned_features_are_refreshed() -> Result<(), Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::from_source(source())
+            .update_interval(Duration::from_millis(100))
+            .failure_policy(FailurePolicy::Closed)
+            .build()
+            .await?;
+
+        poison(&client);
+
+        let result = client.feature_result("guarded-flag", None);
+        assert!(result.off);
+        assert_eq!("failClosed", result.source);
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        assert!(client.is_on("guarded-flag", None));
+
+        Ok(())
+    }
+
+    async fn stale_client(failure_policy: FailurePolicy) -> Result<(PayloadServer, GrowthBookClient), Box<dyn std::error::Error>> {
+        let server = PayloadServer::start().await;
+        server
+            .respond(ResponseTemplate::new(200).set_body_json(json!({ "features": { "guarded-flag": { "defaultValue": true } } })), Some(1))
+            .await;
+        server.respond(ResponseTemplate::new(503), None).await;
+
+        let client = server
+            .builder()
+            .update_interval(Duration::from_millis(100))
+            .retry_policy(RetryPolicy::no_retry())
+            .max_staleness(Duration::from_millis(200))
+            .failure_policy(failure_policy)
+            .build()
+            .await?;
+        Ok((server, client))
+    }
+
+    #[tokio::test]
+    async fn should_apply_failure_policy_once_payload_exceeds_max_staleness() -> Result<(), Box<dyn std::error::Error>> {
+        let (_open_server, open) = stale_client(FailurePolicy::Open).await?;
+        let (_closed_server, closed) = stale_client(FailurePolicy::Closed).await?;
+        assert!(open.is_on("guarded-flag", None));
+        assert!(closed.is_on("guarded-flag", None));
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        assert!(open.is_on("guarded-flag", None));
+        assert!(closed.is_off("guarded-flag", None));
+        let explanation = closed.explain("guarded-flag", None);
+        assert!(explanation.found);
+        assert_eq!("failClosed", explanation.result.source);
+        assert!(!closed.explain("missing-flag", None).found);
+        assert!(!closed.status().ready);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::{client::GrowthBookClientTrait, model_public::GrowthBookAttribute};
+    use rstest::rstest;
+    use serde_json::json;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_rule_id_and_index_when_force_rule_matches(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "country": "BR",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.feature_result("multiple-rules-flag", Some(vec));
+
+        assert_eq!(json!("country"), result.value);
+        assert_eq!(Some(String::from("fr_country")), result.rule_id);
+        assert_eq!(Some(0), result.rule_index);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_rule_id_and_index_when_rollout_rule_matches(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5e-5b0e-75bf-92a3-7658d932634d",
+            "plan": "pro",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.feature_result("multiple-rules-flag", Some(vec));
+
+        assert_eq!(json!("rollout"), result.value);
+        assert_eq!("force", result.source);
+        assert_eq!(Some(String::from("fr_rollout")), result.rule_id);
+        assert_eq!(Some(1), result.rule_index);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_rule_id_and_index_in_experiment_result(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "any-id": "01901d5e-5b0e-75bf-92a3-7658d932634d",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.feature_result("multiple-rules-flag", Some(vec));
+        let experiment_result = result.experiment_result.expect("Failed to get experiment_result");
+
+        assert_eq!("experiment", result.source);
+        assert_eq!(Some(String::from("fr_experiment")), result.rule_id);
+        assert_eq!(Some(2), result.rule_index);
+        assert_eq!(Some(String::from("fr_experiment")), experiment_result.rule_id);
+        assert_eq!(Some(2), experiment_result.rule_index);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_serialize_rule_id_and_index(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = GrowthBookAttribute::from(json!({
+            "country": "BR",
+        }))
+        .expect("Failed to create attributes");
+
+        let result = ctx.growthbook.feature_result("multiple-rules-flag", Some(vec));
+        let json = serde_json::to_value(&result)?;
+
+        assert_eq!(json!("fr_country"), json["ruleId"]);
+        assert_eq!(json!(0), json["ruleIndex"]);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_empty_rule_when_default_value(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let result = ctx.growthbook.feature_result("multiple-rules-flag", None);
+
+        assert_eq!(json!("default"), result.value);
+        assert!(result.rule_id.is_none());
+        assert!(result.rule_index.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::{GrowthBookClient, GrowthBookClientTrait};
+    use growthbook_rust_sdk::source::composite::CompositeSource;
+    use growthbook_rust_sdk::source::file::FileSource;
+    use growthbook_rust_sdk::source::http::HttpSource;
+    use growthbook_rust_sdk::source::static_source::StaticSource;
+    use serde_json::{json, Value};
+    use uuid::Uuid;
+    use wiremock::ResponseTemplate;
+
+    use crate::commons::{PayloadServer, SequenceSource};
+
+    fn payload(flag: &str) -> Value {
+        json!({ "features": { flag: { "defaultValue": true } } })
+    }
+
+    #[tokio::test]
+    async fn should_load_features_from_file_source() -> Result<(), Box<dyn std::error::Error>> {
+        let file_path = std::env::temp_dir().join(format!("growthbook-source-{}.json", Uuid::now_v7()));
+        std::fs::write(&file_path, payload("file-flag").to_string())?;
+
+        let client = GrowthBookClient::from_source(FileSource::new(&file_path)).build().await?;
+
+        assert!(client.is_on("file-flag", None));
+
+        std::fs::remove_file(file_path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_load_features_from_static_source() -> Result<(), Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::from_source(StaticSource::from_value(payload("static-flag"))?).build().await?;
+
+        assert!(client.is_on("static-flag", None));
+        assert_eq!(1, client.total_features());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_load_features_from_custom_source() -> Result<(), Box<dyn std::error::Error>> {
+        let client = GrowthBookClient::from_source(SequenceSource::new(vec![payload("config-service-flag")])).build().await?;
+
+        assert!(client.is_on("config-service-flag", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fall_back_to_next_source_in_composite() -> Result<(), Box<dyn std::error::Error>> {
+        let server = PayloadServer::start().await;
+        server.respond(ResponseTemplate::new(503), None).await;
+        let source = CompositeSource::new()
+            .with_source(HttpSource::new(&server.uri(), server.sdk_key.to_string().as_str(), Duration::from_secs(1))?)
+            .with_source(FileSource::new("/path/that/does/not/exist.json"))
+            .with_source(StaticSource::from_value(payload("fallback-flag"))?);
+
+        let client = GrowthBookClient::from_source(source).build().await?;
+
+        assert!(client.is_on("fallback-flag", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_every_source_fails() -> Result<(), Box<dyn std::error::Error>> {
+        let source = CompositeSource::new().with_source(FileSource::new("/path/that/does/not/exist.json"));
+
+        let result = GrowthBookClient::from_source(source).build().await;
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::attrs;
+    use growthbook_rust_sdk::client::GrowthBookClientTrait;
+    use rstest::rstest;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_true_with_attributes_from_macro(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let any_key = String::from("018fcf64-1827-709a-a8ae-7d206aafb5e2");
+
+        let on = ctx.growthbook.is_on(
+            "flag",
+            Some(attrs! {
+                "any-id" => "018fcf11-bb67-7789-8d10-fcbb7de4ff7b",
+                "any-key" => any_key,
+            }),
+        );
+
+        assert!(on);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_true_with_numeric_attribute_from_macro(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let version: Option<&str> = Some("1.2.2");
+
+        let on = ctx.growthbook.is_on("lte-flag", Some(attrs! { "version" => version, "age" => 30 }));
+
+        assert!(on);
+
+        Ok(())
+    }
+}
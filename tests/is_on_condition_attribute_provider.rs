@@ -0,0 +1,126 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use growthbook_rust_sdk::client::{GrowthBookClient, GrowthBookClientTrait};
+    use growthbook_rust_sdk::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+    use serde_json::json;
+
+    use crate::commons::PayloadServer;
+
+    fn find_value(
+        user_attributes: &[GrowthBookAttribute],
+        key: &str,
+    ) -> Option<GrowthBookAttributeValue> {
+        user_attributes.iter().find(|it| it.key == key).map(|it| it.value.clone())
+    }
+
+    async fn create_client(calls: Arc<AtomicUsize>) -> (PayloadServer, GrowthBookClient) {
+        let body = json!({
+            "features": {
+                "tier-flag": {
+                    "defaultValue": "none",
+                    "rules": [
+                        { "condition": { "tier": "enterprise" }, "force": "enterprise" },
+                        { "condition": { "tier": { "$in": ["pro", "team"] } }, "force": "paid" }
+                    ]
+                },
+                "device-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "device.type": "mobile" }, "force": true }]
+                },
+                "tier-rollout-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "force": true, "coverage": 1.0, "hashAttribute": "tier" }]
+                },
+                "tier-experiment-flag": {
+                    "defaultValue": "none",
+                    "rules": [{ "key": "tier-experiment", "hashAttribute": "tier", "coverage": 1.0, "variations": ["control", "treatment"], "weights": [1.0, 0.0] }]
+                }
+            }
+        });
+        let server = PayloadServer::with_payload(body).await;
+
+        let client = server
+            .builder()
+            .attribute_provider("tier", move |user_attributes: &[GrowthBookAttribute]| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                match find_value(user_attributes, "account_id") {
+                    Some(GrowthBookAttributeValue::Int(1)) => Some(GrowthBookAttributeValue::String(String::from("pro"))),
+                    _ => None,
+                }
+            })
+            .attribute_provider("device", |user_attributes: &[GrowthBookAttribute]| match find_value(user_attributes, "user_agent") {
+                Some(GrowthBookAttributeValue::String(it)) if it.contains("Mobile") => Some(GrowthBookAttributeValue::Object(vec![GrowthBookAttribute::new(
+                    String::from("type"),
+                    GrowthBookAttributeValue::String(String::from("mobile")),
+                )])),
+                _ => None,
+            })
+            .build()
+            .await
+            .expect("Failed to create growthbook client");
+        (server, client)
+    }
+
+    fn attributes(value: serde_json::Value) -> Option<Vec<GrowthBookAttribute>> {
+        Some(GrowthBookAttribute::from(value).expect("Failed to create attributes"))
+    }
+
+    #[tokio::test]
+    async fn should_resolve_computed_attribute_once_per_evaluation() -> Result<(), Box<dyn std::error::Error>> {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let (_server, client) = create_client(Arc::clone(&calls)).await;
+
+        let result = client.feature_result("tier-flag", attributes(json!({ "account_id": 1 })));
+
+        assert_eq!(json!("paid"), result.value);
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+
+        client.feature_result("tier-flag", attributes(json!({ "account_id": 1 })));
+
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_prefer_user_attribute_over_computed_attribute() -> Result<(), Box<dyn std::error::Error>> {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let (_server, client) = create_client(Arc::clone(&calls)).await;
+
+        let result = client.feature_result("tier-flag", attributes(json!({ "account_id": 1, "tier": "enterprise" })));
+
+        assert_eq!(json!("enterprise"), result.value);
+        assert_eq!(0, calls.load(Ordering::SeqCst));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_resolve_nested_path_of_computed_attribute() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client(Arc::new(AtomicUsize::new(0))).await;
+
+        assert!(client.is_on("device-flag", attributes(json!({ "user_agent": "Mozilla/5.0 (iPhone) Mobile/15E148" }))));
+        assert!(!client.is_on("device-flag", attributes(json!({ "user_agent": "Mozilla/5.0 (X11; Linux x86_64)" }))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_hash_computed_attribute() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client(Arc::new(AtomicUsize::new(0))).await;
+
+        assert!(client.is_on("tier-rollout-flag", attributes(json!({ "account_id": 1 }))));
+        assert!(!client.is_on("tier-rollout-flag", attributes(json!({ "account_id": 2 }))));
+
+        let result = client.feature_result("tier-experiment-flag", attributes(json!({ "account_id": 1 })));
+        assert_eq!(json!("control"), result.value);
+        assert_eq!(Some(json!("pro")), result.experiment_result.and_then(|it| it.hash_value));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::net::Ipv4Addr;
+
+    use growthbook_rust_sdk::client::{GrowthBookClient, GrowthBookClientTrait};
+    use growthbook_rust_sdk::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+    use growthbook_rust_sdk::options::CustomOperator;
+    use serde_json::json;
+
+    use crate::commons::PayloadServer;
+
+    struct Cidr;
+
+    impl CustomOperator for Cidr {
+        fn matches(
+            &self,
+            user_value: Option<&GrowthBookAttributeValue>,
+            condition_value: &GrowthBookAttributeValue,
+        ) -> bool {
+            let (Some(GrowthBookAttributeValue::String(ip)), GrowthBookAttributeValue::String(cidr)) = (user_value, condition_value) else {
+                return false;
+            };
+            let Some((network, prefix)) = cidr.split_once('/') else {
+                return false;
+            };
+            match (ip.parse::<Ipv4Addr>(), network.parse::<Ipv4Addr>(), prefix.parse::<u32>()) {
+                (Ok(ip), Ok(network), Ok(prefix)) if prefix <= 32 => {
+                    let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+                    u32::from(ip) & mask == u32::from(network) & mask
+                },
+                _ => false,
+            }
+        }
+    }
+
+    async fn create_client() -> (PayloadServer, GrowthBookClient) {
+        let body = json!({
+            "features": {
+                "cidr-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "ip": { "$cidr": "10.0.0.0/8" } }, "force": true }]
+                },
+                "distance-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "distance_km": { "$near": 50 } }, "force": true }]
+                },
+                "unknown-operator-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "ip": { "$unknown": "10.0.0.0/8" } }, "force": true }]
+                }
+            }
+        });
+        let server = PayloadServer::with_payload(body).await;
+
+        let client = server
+            .builder()
+            .custom_operator("$cidr", Cidr)
+            .custom_operator("near", |user_value: Option<&GrowthBookAttributeValue>, condition_value: &GrowthBookAttributeValue| {
+                match (user_value.and_then(|it| it.as_f64()), condition_value.as_f64()) {
+                    (Some(distance), Some(max_distance)) => distance <= max_distance,
+                    _ => false,
+                }
+            })
+            .build()
+            .await
+            .expect("Failed to create growthbook client");
+        (server, client)
+    }
+
+    fn attributes(value: serde_json::Value) -> Option<Vec<GrowthBookAttribute>> {
+        Some(GrowthBookAttribute::from(value).expect("Failed to create attributes"))
+    }
+
+    #[tokio::test]
+    async fn should_dispatch_to_custom_operator_trait_object() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client().await;
+
+        assert!(client.is_on("cidr-flag", attributes(json!({ "ip": "10.20.30.40" }))));
+        assert!(!client.is_on("cidr-flag", attributes(json!({ "ip": "192.168.0.1" }))));
+        assert!(!client.is_on("cidr-flag", None));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_dispatch_to_custom_operator_closure() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client().await;
+
+        assert!(client.is_on("distance-flag", attributes(json!({ "distance_km": 12.5 }))));
+        assert!(!client.is_on("distance-flag", attributes(json!({ "distance_km": 120 }))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_closed_for_unregistered_operator() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client().await;
+
+        assert!(!client.is_on("unknown-operator-flag", attributes(json!({ "ip": "10.20.30.40" }))));
+
+        Ok(())
+    }
+}
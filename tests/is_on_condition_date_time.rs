@@ -0,0 +1,109 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, TimeZone, Utc};
+    use growthbook_rust_sdk::client::GrowthBookClientTrait;
+    use growthbook_rust_sdk::model_public::{GrowthBookAttribute, GrowthBookAttributeValue};
+    use rstest::rstest;
+    use test_context::test_context;
+
+    use crate::commons::TestContext;
+
+    fn date_time(value: &str) -> GrowthBookAttributeValue {
+        GrowthBookAttributeValue::DateTime(DateTime::parse_from_rfc3339(value).expect("Failed to parse date time").with_timezone(&Utc))
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_true_when_date_time_is_after_in_another_offset(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = vec![GrowthBookAttribute::new(String::from("signup_date"), date_time("2024-01-01T05:00:00+01:00"))];
+
+        let on = ctx.growthbook.is_on("date-time-gt-rule", Some(vec));
+
+        assert!(on);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_false_when_date_time_is_before_in_another_offset(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let vec = vec![GrowthBookAttribute::new(String::from("signup_date"), date_time("2024-01-01T03:00:00+01:00"))];
+
+        let on = ctx.growthbook.is_on("date-time-gt-rule", Some(vec));
+
+        assert!(!on);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_true_when_date_time_is_before_epoch(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let expires_at = Utc.with_ymd_and_hms(2024, 12, 31, 23, 59, 59).single().expect("Failed to create date time");
+        let vec = vec![GrowthBookAttribute::new(String::from("subscription_expires_at"), GrowthBookAttributeValue::DateTime(expires_at))];
+
+        let on = ctx.growthbook.is_on("date-time-lt-epoch-rule", Some(vec));
+
+        assert!(on);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_return_enabled_false_when_date_time_is_after_epoch(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let expires_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).single().expect("Failed to create date time");
+        let vec = vec![GrowthBookAttribute::new(String::from("subscription_expires_at"), GrowthBookAttributeValue::DateTime(expires_at))];
+
+        let on = ctx.growthbook.is_on("date-time-lt-epoch-rule", Some(vec));
+
+        assert!(!on);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_compare_iso_string_attribute_as_instant(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        // 01:00Z is before the 03:00Z condition, although it sorts after it as a string
+        let vec = vec![GrowthBookAttribute::new(
+            String::from("signup_date"),
+            GrowthBookAttributeValue::String(String::from("2024-01-01T02:00:00+01:00")),
+        )];
+
+        let on = ctx.growthbook.is_on("date-time-gt-rule", Some(vec));
+
+        assert!(!on);
+
+        Ok(())
+    }
+
+    #[test_context(TestContext)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_compare_millisecond_epoch(ctx: &mut TestContext) -> Result<(), Box<dyn std::error::Error>> {
+        let before = Utc.with_ymd_and_hms(2024, 12, 31, 23, 59, 59).single().expect("Failed to create date time");
+        let after = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).single().expect("Failed to create date time");
+
+        let on_before = ctx.growthbook.is_on(
+            "date-time-lt-epoch-millis-rule",
+            Some(vec![GrowthBookAttribute::new(String::from("subscription_expires_at"), GrowthBookAttributeValue::DateTime(before))]),
+        );
+        let on_after = ctx.growthbook.is_on(
+            "date-time-lt-epoch-millis-rule",
+            Some(vec![GrowthBookAttribute::new(String::from("subscription_expires_at"), GrowthBookAttributeValue::DateTime(after))]),
+        );
+
+        assert!(on_before);
+        assert!(!on_after);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use growthbook_rust_sdk::client::{GrowthBookClient, GrowthBookClientBuilder, GrowthBookClientTrait};
+    use growthbook_rust_sdk::model_public::{GrowthBookAttribute, ToGrowthBookAttributes};
+    use serde_json::json;
+
+    use crate::commons::PayloadServer;
+
+    const USER_EMAIL_HASH: &str = "9c8ef7b5f590a926f0f3a6ea6a3941196fe272a834a7915b0a733b46412caf42";
+    const OTHER_EMAIL_HASH: &str = "2e9edd1e7fcf7c7f71decb7932ec4ac1974769a9c97ae2ef15962a72ec5d6379";
+    const BETA_HASH: &str = "518f00f06594d90ab3a358a059d9899a9d8703498f8bd5b23834c10ecbb892c2";
+    const VIP_HASH: &str = "f4467f6e6c9e4ec34d0c837811754ce87af55080178211b9ceb33327dd16a423";
+
+    struct User {
+        email: String,
+    }
+
+    impl ToGrowthBookAttributes for User {
+        fn to_growthbook_attributes(&self) -> Vec<GrowthBookAttribute> {
+            vec![GrowthBookAttribute::new(String::from("email"), self.email.as_str().into())]
+        }
+
+        fn secure_attribute_keys() -> Vec<String> {
+            vec![String::from("email")]
+        }
+    }
+
+    async fn create_client(secure: bool) -> (PayloadServer, GrowthBookClient) {
+        create_client_with(|builder| {
+            if secure {
+                builder.secure_attributes("gb-salt", vec![String::from("email"), String::from("tags")])
+            } else {
+                builder
+            }
+        })
+        .await
+    }
+
+    async fn create_client_with(configure: impl FnOnce(GrowthBookClientBuilder) -> GrowthBookClientBuilder) -> (PayloadServer, GrowthBookClient) {
+        let body = json!({
+            "features": {
+                "secure-eq-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "email": USER_EMAIL_HASH }, "force": true }]
+                },
+                "secure-in-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "email": { "$in": [USER_EMAIL_HASH, OTHER_EMAIL_HASH] } }, "force": true }]
+                },
+                "secure-nin-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "email": { "$nin": [OTHER_EMAIL_HASH] } }, "force": true }]
+                },
+                "secure-regex-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "email": { "$regex": "@example\\.com$" } }, "force": true }]
+                },
+                "secure-all-flag": {
+                    "defaultValue": false,
+                    "rules": [{ "condition": { "tags": { "$all": [BETA_HASH, VIP_HASH] } }, "force": true }]
+                }
+            }
+        });
+        let server = PayloadServer::with_payload(body).await;
+
+        let client = configure(server.builder()).build().await.expect("Failed to create growthbook client");
+        (server, client)
+    }
+
+    fn attributes() -> Option<Vec<GrowthBookAttribute>> {
+        Some(
+            GrowthBookAttribute::from(json!({
+                "email": "user@example.com",
+                "tags": ["beta", "vip", "other"],
+            }))
+            .expect("Failed to create attributes"),
+        )
+    }
+
+    #[tokio::test]
+    async fn should_match_hashed_secure_attributes() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client(true).await;
+
+        assert!(client.is_on("secure-eq-flag", attributes()));
+        assert!(client.is_on("secure-in-flag", attributes()));
+        assert!(client.is_on("secure-nin-flag", attributes()));
+        assert!(client.is_on("secure-all-flag", attributes()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_hash_secure_attributes_for_other_operators() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client(true).await;
+
+        assert!(client.is_on("secure-regex-flag", attributes()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_match_secure_attributes_without_salt() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client(false).await;
+
+        assert!(!client.is_on("secure-eq-flag", attributes()));
+        assert!(!client.is_on("secure-in-flag", attributes()));
+        assert!(!client.is_on("secure-all-flag", attributes()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_combine_derived_secure_keys_with_explicit_keys() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client_with(|builder| builder.secure_attributes_for::<User>("gb-salt").secure_attributes("gb-salt", vec![String::from("tags")])).await;
+
+        let user = User {
+            email: String::from("user@example.com"),
+        };
+        assert!(client.is_on("secure-eq-flag", Some(user.to_growthbook_attributes())));
+        assert!(client.is_on("secure-all-flag", attributes()));
+
+        Ok(())
+    }
+}
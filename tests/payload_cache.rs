@@ -0,0 +1,68 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClientTrait;
+    use serde_json::json;
+    use uuid::Uuid;
+    use wiremock::ResponseTemplate;
+
+    use crate::commons::PayloadServer;
+
+    async fn offline_server() -> PayloadServer {
+        let server = PayloadServer::start().await;
+        server.respond(ResponseTemplate::new(503), None).await;
+        server
+    }
+
+    fn cache_path(sdk_key: Uuid) -> PathBuf {
+        std::env::temp_dir().join(format!("growthbook-{sdk_key}.json"))
+    }
+
+    #[tokio::test]
+    async fn should_load_cached_payload_when_first_fetch_fails() -> Result<(), Box<dyn std::error::Error>> {
+        let online_server = PayloadServer::with_payload(json!({ "features": { "cached-flag": { "defaultValue": true } } })).await;
+        let cache_path = cache_path(online_server.sdk_key);
+
+        let online_client = online_server.builder().payload_cache(&cache_path).build().await?;
+        assert!(online_client.is_on("cached-flag", None));
+        assert!(!online_client.is_stale());
+        assert!(cache_path.exists());
+
+        let offline_server = offline_server().await;
+        let offline_client = offline_server.builder().payload_cache(&cache_path).payload_cache_max_age(Duration::from_secs(3600)).build().await?;
+
+        assert!(offline_client.is_on("cached-flag", None));
+        assert!(offline_client.is_stale());
+
+        std::fs::remove_file(cache_path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_first_fetch_fails_and_cache_is_corrupted() -> Result<(), Box<dyn std::error::Error>> {
+        let offline_server = offline_server().await;
+        let cache_path = cache_path(offline_server.sdk_key);
+        std::fs::write(&cache_path, "not a payload")?;
+
+        let result = offline_server.builder().payload_cache(&cache_path).build().await;
+
+        assert!(result.is_err());
+
+        std::fs::remove_file(cache_path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_first_fetch_fails_without_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let offline_server = offline_server().await;
+
+        let result = offline_server.builder().build().await;
+
+        assert!(result.is_err());
+        Ok(())
+    }
+}
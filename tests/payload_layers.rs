@@ -0,0 +1,129 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClientTrait;
+    use growthbook_rust_sdk::layer::model::{LayerPrecedence, PayloadLayer};
+    use growthbook_rust_sdk::model_public::GrowthBookAttribute;
+    use growthbook_rust_sdk::source::file::FileSource;
+    use growthbook_rust_sdk::source::static_source::StaticSource;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use crate::commons::PayloadServer;
+
+    async fn remote() -> PayloadServer {
+        PayloadServer::with_payload(json!({
+            "features": {
+                "remote-flag": { "defaultValue": true },
+                "replaced-flag": { "defaultValue": false },
+                "layered-flag": {
+                    "defaultValue": "remote",
+                    "rules": [{ "id": "fr_remote", "condition": { "country": "BR" }, "force": "remote-rule" }]
+                }
+            }
+        }))
+        .await
+    }
+
+    #[tokio::test]
+    async fn should_apply_layers_on_top_of_remote_payload() -> Result<(), Box<dyn std::error::Error>> {
+        let server = remote().await;
+        let overrides = StaticSource::from_value(json!({
+            "features": {
+                "replaced-flag": { "defaultValue": true },
+                "staging-only-flag": { "defaultValue": "on" }
+            }
+        }))?;
+        let rules = StaticSource::from_value(json!({
+            "features": {
+                "layered-flag": { "rules": [{ "id": "fr_staging", "condition": { "tester": true }, "force": "staging-rule" }] }
+            }
+        }))?;
+
+        let client = server
+            .builder()
+            .layer(PayloadLayer::new("overrides", overrides))
+            .layer(PayloadLayer::new("staging-rules", rules).precedence(LayerPrecedence::Rule))
+            .build()
+            .await?;
+
+        let remote = client.feature_result("remote-flag", None);
+        assert!(remote.on);
+        assert_eq!(None, remote.layer);
+
+        let replaced = client.feature_result("replaced-flag", None);
+        assert!(replaced.on);
+        assert_eq!(Some(String::from("overrides")), replaced.layer);
+
+        let added = client.feature_result("staging-only-flag", None);
+        assert_eq!(json!("on"), added.value);
+        assert_eq!(Some(String::from("overrides")), added.layer);
+
+        let tester = Some(GrowthBookAttribute::from(json!({ "tester": true, "country": "BR" }))?);
+        let staging_rule = client.feature_result("layered-flag", tester);
+        assert_eq!(json!("staging-rule"), staging_rule.value);
+        assert_eq!(Some(String::from("staging-rules")), staging_rule.layer);
+
+        let country = Some(GrowthBookAttribute::from(json!({ "country": "BR" }))?);
+        let remote_rule = client.feature_result("layered-flag", country);
+        assert_eq!(json!("remote-rule"), remote_rule.value);
+        assert_eq!(Some(String::from("fr_remote")), remote_rule.rule_id);
+        assert_eq!(None, remote_rule.layer);
+
+        let default = client.feature_result("layered-flag", None);
+        assert_eq!(json!("remote"), default.value);
+        assert_eq!(None, default.layer);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reapply_file_layer_on_refresh() -> Result<(), Box<dyn std::error::Error>> {
+        let server = remote().await;
+        let file_path = std::env::temp_dir().join(format!("growthbook-layer-{}.json", Uuid::now_v7()));
+        std::fs::write(&file_path, json!({ "features": { "remote-flag": { "defaultValue": false } } }).to_string())?;
+
+        let client = server
+            .builder()
+            .update_interval(Duration::from_millis(100))
+            .layer(PayloadLayer::new("staging", FileSource::new(&file_path)))
+            .build()
+            .await?;
+        assert!(client.is_off("remote-flag", None));
+
+        std::fs::write(&file_path, json!({ "features": { "remote-flag": { "defaultValue": true } } }).to_string())?;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let result = client.feature_result("remote-flag", None);
+        assert!(result.on);
+        assert_eq!(Some(String::from("staging")), result.layer);
+
+        std::fs::remove_file(file_path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_keep_last_layer_payload_when_layer_fails() -> Result<(), Box<dyn std::error::Error>> {
+        let server = remote().await;
+        let file_path = std::env::temp_dir().join(format!("growthbook-layer-{}.json", Uuid::now_v7()));
+        std::fs::write(&file_path, json!({ "features": { "remote-flag": { "defaultValue": false } } }).to_string())?;
+
+        let client = server
+            .builder()
+            .update_interval(Duration::from_millis(100))
+            .layer(PayloadLayer::new("staging", FileSource::new(&file_path)))
+            .build()
+            .await?;
+
+        std::fs::write(&file_path, "not a payload")?;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        assert!(client.is_off("remote-flag", None));
+
+        std::fs::remove_file(file_path)?;
+        Ok(())
+    }
+}
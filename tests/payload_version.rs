@@ -0,0 +1,89 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use chrono::{DateTime, Utc};
+    use growthbook_rust_sdk::client::GrowthBookClientTrait;
+    use growthbook_rust_sdk::layer::model::PayloadLayer;
+    use growthbook_rust_sdk::refresh::model::RetryPolicy;
+    use serde_json::json;
+    use wiremock::ResponseTemplate;
+
+    use crate::commons::{PayloadServer, SequenceSource};
+
+    async fn server(
+        first: (&str, bool),
+        then: (&str, bool),
+    ) -> PayloadServer {
+        let server = PayloadServer::start().await;
+        for (date_updated, enabled, times) in [(first.0, first.1, Some(1)), (then.0, then.1, None)] {
+            let payload = json!({
+                "features": { "versioned-flag": { "defaultValue": enabled } },
+                "dateUpdated": date_updated
+            });
+            server.respond(ResponseTemplate::new(200).set_body_json(payload), times).await;
+        }
+        server
+    }
+
+    #[tokio::test]
+    async fn should_skip_payload_older_than_current() -> Result<(), Box<dyn std::error::Error>> {
+        let server = server(("2024-06-01T10:00:00.000Z", true), ("2024-05-01T10:00:00.000Z", false)).await;
+
+        let client = server.builder().update_interval(Duration::from_millis(100)).retry_policy(RetryPolicy::no_retry()).build().await?;
+        let mut updates = client.subscribe();
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        assert!(client.is_on("versioned-flag", None));
+        assert_eq!(Some("2024-06-01T10:00:00Z".parse::<DateTime<Utc>>()?), client.payload_version());
+        assert!(updates.try_recv().is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_apply_newer_payload() -> Result<(), Box<dyn std::error::Error>> {
+        let server = server(("2024-05-01T10:00:00.000Z", false), ("2024-06-01T10:00:00.000Z", true)).await;
+
+        let client = server.builder().update_interval(Duration::from_millis(100)).build().await?;
+        let mut updates = client.subscribe();
+
+        tokio::time::timeout(Duration::from_secs(3), updates.recv()).await??;
+
+        assert!(client.is_on("versioned-flag", None));
+        assert_eq!(Some("2024-06-01T10:00:00Z".parse::<DateTime<Utc>>()?), client.payload_version());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refresh_layers_when_payload_is_skipped() -> Result<(), Box<dyn std::error::Error>> {
+        let server = server(("2024-06-01T10:00:00.000Z", true), ("2024-05-01T10:00:00.000Z", false)).await;
+        let layer = SequenceSource::new(vec![
+            json!({ "features": { "layer-flag": { "defaultValue": false } } }),
+            json!({ "features": { "layer-flag": { "defaultValue": true } } }),
+        ]);
+
+        let client = server
+            .builder()
+            .update_interval(Duration::from_millis(100))
+            .retry_policy(RetryPolicy::no_retry())
+            .layer(PayloadLayer::new("overrides", layer))
+            .build()
+            .await?;
+        assert!(!client.is_on("layer-flag", None));
+        assert_eq!(None, client.status().last_skipped);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        assert!(client.is_on("layer-flag", None));
+        assert!(client.is_on("versioned-flag", None));
+        let skipped = client.status().last_skipped.expect("Skip was not recorded");
+        assert_eq!(Some("2024-05-01T10:00:00Z".parse::<DateTime<Utc>>()?), skipped.date_updated);
+
+        Ok(())
+    }
+}
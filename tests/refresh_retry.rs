@@ -0,0 +1,118 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::error::GrowthbookErrorCode;
+    use growthbook_rust_sdk::refresh::model::RetryPolicy;
+    use growthbook_rust_sdk::source::http::HttpSource;
+    use growthbook_rust_sdk::source::FeatureSource;
+    use tokio::time::Instant;
+    use wiremock::ResponseTemplate;
+
+    use crate::commons::{PayloadServer, SequenceSource};
+
+    fn deterministic_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.0,
+            interval_jitter: 0.0,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_retry_with_exponential_backoff() -> Result<(), Box<dyn std::error::Error>> {
+        let source = SequenceSource::failing(vec![None, None]);
+        let start = Instant::now();
+
+        let _client = GrowthBookClient::from_source(source.clone())
+            .update_interval(Duration::from_secs(60))
+            .retry_policy(deterministic_policy())
+            .build()
+            .await?;
+        tokio::time::sleep(Duration::from_secs(70)).await;
+
+        assert_eq!(vec![0, 0, 1, 3, 63], source.elapsed_seconds(start));
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_stop_retrying_after_max_attempts() -> Result<(), Box<dyn std::error::Error>> {
+        let source = SequenceSource::failing(vec![None, None, None, None]);
+        let start = Instant::now();
+
+        let _client = GrowthBookClient::from_source(source.clone())
+            .update_interval(Duration::from_secs(60))
+            .retry_policy(deterministic_policy())
+            .build()
+            .await?;
+        tokio::time::sleep(Duration::from_secs(70)).await;
+
+        // the third failure ends the cycle, the next one starts after the update interval
+        assert_eq!(vec![0, 0, 1, 3, 63, 64], source.elapsed_seconds(start));
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_honour_retry_after() -> Result<(), Box<dyn std::error::Error>> {
+        let source = SequenceSource::failing(vec![Some(Duration::from_secs(10)), None, Some(Duration::from_secs(120))]);
+        let start = Instant::now();
+
+        let _client = GrowthBookClient::from_source(source.clone())
+            .update_interval(Duration::from_secs(60))
+            .retry_policy(deterministic_policy())
+            .build()
+            .await?;
+        tokio::time::sleep(Duration::from_secs(150)).await;
+
+        // waits 10s instead of 1s, then 2s, and 120s instead of the 60s interval after the cycle fails
+        assert_eq!(vec![0, 0, 10, 12, 132], source.elapsed_seconds(start));
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_spread_polling_with_interval_jitter() -> Result<(), Box<dyn std::error::Error>> {
+        let source = SequenceSource::failing(vec![]);
+        let start = Instant::now();
+
+        let _client = GrowthBookClient::from_source(source.clone())
+            .update_interval(Duration::from_secs(100))
+            .retry_policy(RetryPolicy {
+                interval_jitter: 0.5,
+                ..deterministic_policy()
+            })
+            .build()
+            .await?;
+        tokio::time::sleep(Duration::from_secs(1000)).await;
+
+        let calls = source.elapsed_seconds(start);
+        let intervals: Vec<_> = calls.windows(2).skip(1).map(|pair| pair[1] - pair[0]).collect();
+        assert!(intervals.len() >= 10);
+        assert!(intervals.iter().all(|interval| (49..=100).contains(interval)), "{intervals:?}");
+        assert!(intervals.iter().any(|interval| *interval != intervals[0]), "{intervals:?}");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_read_retry_after_header() -> Result<(), Box<dyn std::error::Error>> {
+        let server = PayloadServer::start().await;
+        server.respond(ResponseTemplate::new(429).insert_header("Retry-After", "120"), None).await;
+        let source = HttpSource::new(&server.uri(), server.sdk_key.to_string().as_str(), Duration::from_secs(1))?;
+
+        let error = source.fetch().await.expect_err("rate limited fetch should fail");
+
+        assert!(matches!(error.code, GrowthbookErrorCode::GrowthbookGateway));
+        assert_eq!(Some(Duration::from_secs(120)), error.retry_after);
+
+        Ok(())
+    }
+}
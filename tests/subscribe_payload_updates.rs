@@ -0,0 +1,86 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::updates::model::{FeatureChange, PayloadDiff, RuleChange, RuleChangeKind};
+    use serde_json::json;
+    use wiremock::ResponseTemplate;
+
+    use crate::commons::PayloadServer;
+
+    #[tokio::test]
+    async fn should_notify_subscribers_with_payload_diff() -> Result<(), Box<dyn std::error::Error>> {
+        let server = PayloadServer::start().await;
+        server
+            .respond(
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "features": {
+                        "kept-feature": { "defaultValue": true },
+                        "removed-feature": { "defaultValue": true },
+                        "changed-feature": {
+                            "defaultValue": false,
+                            "rules": [{ "id": "fr_country", "condition": { "country": "BR" }, "force": true }]
+                        }
+                    }
+                })),
+                Some(1),
+            )
+            .await;
+        server
+            .respond(
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "features": {
+                        "kept-feature": { "defaultValue": true },
+                        "added-feature": { "defaultValue": false },
+                        "changed-feature": {
+                            "defaultValue": false,
+                            "rules": [
+                                { "id": "fr_country", "condition": { "country": "US" }, "force": true },
+                                { "id": "fr_plan", "condition": { "plan": "pro" }, "force": true }
+                            ]
+                        }
+                    }
+                })),
+                None,
+            )
+            .await;
+
+        let client = server.builder().update_interval(Duration::from_secs(1)).build().await?;
+        let mut updates = client.subscribe();
+
+        let update = tokio::time::timeout(Duration::from_secs(3), updates.recv()).await??;
+
+        assert_eq!(1, update.version);
+        assert_eq!(
+            PayloadDiff {
+                added: vec![String::from("added-feature")],
+                removed: vec![String::from("removed-feature")],
+                changed: vec![FeatureChange {
+                    feature_id: String::from("changed-feature"),
+                    default_value_changed: false,
+                    rules: vec![
+                        RuleChange {
+                            index: 0,
+                            id: Some(String::from("fr_country")),
+                            kind: RuleChangeKind::Changed,
+                        },
+                        RuleChange {
+                            index: 1,
+                            id: Some(String::from("fr_plan")),
+                            kind: RuleChangeKind::Added,
+                        },
+                    ],
+                }],
+                forced_variations: vec![],
+            },
+            update.diff
+        );
+
+        let unchanged = tokio::time::timeout(Duration::from_secs(2), updates.recv()).await;
+        assert!(unchanged.is_err(), "unchanged payloads must not be notified");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use growthbook_rust_sdk::options::EvaluationDiagnostic;
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    use crate::commons::SequenceSource;
+
+    #[derive(Deserialize, Debug, Clone, PartialEq)]
+    struct RetryConfig {
+        attempts: u32,
+        backoff_ms: u64,
+    }
+
+    fn payload(value: Value) -> Value {
+        json!({
+            "features": {
+                "retry-config": {
+                    "defaultValue": value,
+                    "rules": [{ "condition": { "tenant": "beta" }, "force": { "attempts": 9, "backoff_ms": 900 } }]
+                }
+            }
+        })
+    }
+
+    async fn create_client(
+        first: Value,
+        second: Value,
+        diagnostics: Arc<Mutex<Vec<EvaluationDiagnostic>>>,
+    ) -> GrowthBookClient {
+        GrowthBookClient::from_source(SequenceSource::new(vec![payload(first), payload(second)]))
+            .update_interval(Duration::from_secs(1))
+            .diagnostics(move |diagnostic| diagnostics.lock().expect("Failed to lock").push(diagnostic.clone()))
+            .build()
+            .await
+            .expect("Failed to create growthbook client")
+    }
+
+    fn default_config() -> RetryConfig {
+        RetryConfig { attempts: 1, backoff_ms: 100 }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_update_config_when_payload_changes() -> Result<(), Box<dyn std::error::Error>> {
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let client = create_client(json!({ "attempts": 3, "backoff_ms": 250 }), json!({ "attempts": 5, "backoff_ms": 500 }), Arc::clone(&diagnostics)).await;
+
+        let handle = client.watch_config::<RetryConfig>("retry-config", None, default_config());
+        let beta_handle = client.watch_config::<RetryConfig>("retry-config", Some(growthbook_rust_sdk::attrs! { "tenant" => "beta" }), default_config());
+
+        assert_eq!(RetryConfig { attempts: 3, backoff_ms: 250 }, *handle.get());
+        assert_eq!(RetryConfig { attempts: 9, backoff_ms: 900 }, *beta_handle.get());
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        assert_eq!(RetryConfig { attempts: 5, backoff_ms: 500 }, *handle.get());
+        assert_eq!(None, handle.last_error());
+        assert!(diagnostics.lock().expect("Failed to lock").is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_keep_last_good_config_when_new_payload_fails_to_deserialize() -> Result<(), Box<dyn std::error::Error>> {
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let client = create_client(json!({ "attempts": 3, "backoff_ms": 250 }), json!({ "attempts": "three" }), Arc::clone(&diagnostics)).await;
+
+        let handle = client.watch_config::<RetryConfig>("retry-config", None, default_config());
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        assert_eq!(RetryConfig { attempts: 3, backoff_ms: 250 }, *handle.get());
+        assert!(handle.last_error().is_some());
+        assert!(matches!(
+            diagnostics.lock().expect("Failed to lock").as_slice(),
+            [EvaluationDiagnostic::ConfigDeserializationFailed { feature, .. }] if feature == "retry-config"
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_use_default_when_first_payload_fails_to_deserialize() -> Result<(), Box<dyn std::error::Error>> {
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let client = create_client(json!("invalid"), json!({ "attempts": 5, "backoff_ms": 500 }), Arc::clone(&diagnostics)).await;
+
+        let handle = client.watch_config::<RetryConfig>("retry-config", None, default_config());
+
+        assert_eq!(default_config(), *handle.get());
+        assert!(handle.last_error().is_some());
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        assert_eq!(RetryConfig { attempts: 5, backoff_ms: 500 }, *handle.get());
+        assert_eq!(None, handle.last_error());
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_stop_update_task_when_handle_is_dropped() -> Result<(), Box<dyn std::error::Error>> {
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let client = create_client(json!({ "attempts": 3, "backoff_ms": 250 }), json!({ "attempts": 5, "backoff_ms": 500 }), Arc::clone(&diagnostics)).await;
+        let references = Arc::strong_count(&client.gb);
+
+        let handle = client.watch_config::<RetryConfig>("retry-config", None, default_config());
+        let cloned = handle.clone();
+        assert_eq!(references + 1, Arc::strong_count(&client.gb));
+
+        drop(handle);
+        tokio::task::yield_now().await;
+        assert_eq!(references + 1, Arc::strong_count(&client.gb));
+
+        drop(cloned);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(references, Arc::strong_count(&client.gb));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use futures_util::StreamExt;
+    use growthbook_rust_sdk::attrs;
+    use growthbook_rust_sdk::client::GrowthBookClient;
+    use serde_json::{json, Value};
+    use wiremock::ResponseTemplate;
+
+    use crate::commons::PayloadServer;
+
+    fn payload(default_value: bool) -> Value {
+        json!({
+            "features": {
+                "watched-flag": {
+                    "defaultValue": default_value,
+                    "rules": [{ "condition": { "country": "BR" }, "force": true }]
+                }
+            }
+        })
+    }
+
+    async fn create_client() -> (PayloadServer, GrowthBookClient) {
+        let server = PayloadServer::start().await;
+        server.respond(ResponseTemplate::new(200).set_body_json(payload(false)), Some(1)).await;
+        server.respond(ResponseTemplate::new(200).set_body_json(payload(true)), None).await;
+
+        let client = server.builder().update_interval(Duration::from_secs(1)).build().await.expect("Failed to create growthbook client");
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn should_emit_initial_value_and_changes() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client().await;
+
+        let mut stream = client.watch_feature("watched-flag", Some(attrs! { "country" => "US" }));
+
+        let initial = tokio::time::timeout(Duration::from_secs(1), stream.next()).await?.expect("Stream ended");
+        assert!(!initial.on);
+
+        let changed = tokio::time::timeout(Duration::from_secs(3), stream.next()).await?.expect("Stream ended");
+        assert!(changed.on);
+        assert_eq!("defaultValue", changed.source);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_emit_when_evaluated_value_is_unchanged() -> Result<(), Box<dyn std::error::Error>> {
+        let (_server, client) = create_client().await;
+
+        let mut stream = client.watch_feature("watched-flag", Some(attrs! { "country" => "BR" }));
+
+        let initial = tokio::time::timeout(Duration::from_secs(1), stream.next()).await?.expect("Stream ended");
+        assert!(initial.on);
+
+        let next = tokio::time::timeout(Duration::from_secs(3), stream.next()).await;
+        assert!(next.is_err(), "unchanged value must not be emitted");
+
+        Ok(())
+    }
+}
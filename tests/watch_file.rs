@@ -0,0 +1,138 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::{GrowthBookClient, GrowthBookClientTrait};
+    use growthbook_rust_sdk::options::EvaluationDiagnostic;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use crate::commons::eventually;
+
+    fn write_atomically(
+        path: &Path,
+        contents: &str,
+    ) -> std::io::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, contents)?;
+        std::fs::rename(tmp_path, path)
+    }
+
+    #[tokio::test]
+    async fn should_reload_features_when_file_changes() -> Result<(), Box<dyn std::error::Error>> {
+        let directory = std::env::temp_dir().join(format!("growthbook-watch-{}", Uuid::now_v7()));
+        std::fs::create_dir_all(&directory)?;
+        let file_path = directory.join("features.json");
+        std::fs::write(&file_path, json!({ "features": { "watched-flag": { "defaultValue": false } } }).to_string())?;
+
+        let client = GrowthBookClient::from_file(&file_path).update_interval(Duration::from_secs(600)).build().await?;
+        let mut updates = client.subscribe();
+        assert!(client.is_off("watched-flag", None));
+
+        write_atomically(&file_path, &json!({ "features": { "watched-flag": { "defaultValue": true } } }).to_string())?;
+
+        let update = tokio::time::timeout(Duration::from_secs(5), updates.recv()).await??;
+        assert!(update.diff.contains("watched-flag"));
+        assert!(client.is_on("watched-flag", None));
+
+        std::fs::write(&file_path, json!({ "features": { "watched-flag": { "defaultValue": false } } }).to_string())?;
+
+        assert!(eventually(Duration::from_secs(5), || client.is_off("watched-flag", None)).await);
+
+        std::fs::remove_dir_all(directory)?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn should_reload_features_when_config_map_is_swapped() -> Result<(), Box<dyn std::error::Error>> {
+        use std::os::unix::fs::symlink;
+
+        // mirrors the layout kubelet uses for mounted ConfigMaps
+        let directory = std::env::temp_dir().join(format!("growthbook-config-map-{}", Uuid::now_v7()));
+        std::fs::create_dir_all(directory.join("..v1"))?;
+        std::fs::write(
+            directory.join("..v1/features.json"),
+            json!({ "features": { "config-map-flag": { "defaultValue": false } } }).to_string(),
+        )?;
+        symlink("..v1", directory.join("..data"))?;
+        symlink("..data/features.json", directory.join("features.json"))?;
+
+        let client = GrowthBookClient::from_file(directory.join("features.json")).update_interval(Duration::from_secs(600)).build().await?;
+        assert!(client.is_off("config-map-flag", None));
+
+        std::fs::create_dir_all(directory.join("..v2"))?;
+        std::fs::write(directory.join("..v2/features.json"), json!({ "features": { "config-map-flag": { "defaultValue": true } } }).to_string())?;
+        symlink("..v2", directory.join("..data_tmp"))?;
+        std::fs::rename(directory.join("..data_tmp"), directory.join("..data"))?;
+
+        assert!(eventually(Duration::from_secs(5), || client.is_on("config-map-flag", None)).await);
+
+        std::fs::remove_dir_all(directory)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_keep_last_good_payload_on_parse_error() -> Result<(), Box<dyn std::error::Error>> {
+        let directory = std::env::temp_dir().join(format!("growthbook-watch-{}", Uuid::now_v7()));
+        std::fs::create_dir_all(&directory)?;
+        let file_path = directory.join("features.json");
+        std::fs::write(&file_path, json!({ "features": { "watched-flag": { "defaultValue": true } } }).to_string())?;
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let collected = Arc::clone(&diagnostics);
+
+        let client = GrowthBookClient::from_file(&file_path)
+            .update_interval(Duration::from_secs(600))
+            .diagnostics(move |diagnostic| collected.lock().unwrap().push(diagnostic.clone()))
+            .build()
+            .await?;
+
+        write_atomically(&file_path, "{ \"features\": ")?;
+
+        assert!(eventually(Duration::from_secs(5), || !diagnostics.lock().unwrap().is_empty()).await);
+        assert!(matches!(
+            diagnostics.lock().unwrap().first(),
+            Some(EvaluationDiagnostic::PayloadReloadFailed { path, .. }) if path.ends_with("features.json")
+        ));
+        assert!(client.is_on("watched-flag", None));
+
+        std::fs::remove_dir_all(directory)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_stop_watching_when_client_is_dropped() -> Result<(), Box<dyn std::error::Error>> {
+        let directory = std::env::temp_dir().join(format!("growthbook-watch-{}", Uuid::now_v7()));
+        std::fs::create_dir_all(&directory)?;
+        let file_path = directory.join("features.json");
+        std::fs::write(&file_path, json!({ "features": { "watched-flag": { "defaultValue": true } } }).to_string())?;
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let collected = Arc::clone(&diagnostics);
+
+        let client = GrowthBookClient::from_file(&file_path)
+            .update_interval(Duration::from_secs(600))
+            .diagnostics(move |diagnostic| collected.lock().unwrap().push(diagnostic.clone()))
+            .build()
+            .await?;
+        drop(client);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        write_atomically(&file_path, "{ \"features\": ")?;
+
+        assert!(!eventually(Duration::from_secs(1), || !diagnostics.lock().unwrap().is_empty()).await);
+
+        std::fs::remove_dir_all(directory)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_to_build_when_file_does_not_exist() {
+        let result = GrowthBookClient::from_file("/path/that/does/not/exist/features.json").build().await;
+
+        assert!(result.is_err());
+    }
+}
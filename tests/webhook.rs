@@ -0,0 +1,84 @@
+mod commons;
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use growthbook_rust_sdk::client::{GrowthBookClient, GrowthBookClientTrait};
+    use growthbook_rust_sdk::error::{GrowthbookError, GrowthbookErrorCode};
+    use growthbook_rust_sdk::webhook::WebhookHandler;
+    use serde_json::{json, Value};
+
+    use crate::commons::SequenceSource;
+
+    const SECRET: &str = "test-webhook-secret";
+
+    fn payload(enabled: bool) -> Value {
+        json!({ "features": { "webhook-flag": { "defaultValue": enabled } } })
+    }
+
+    async fn client(source: &SequenceSource) -> Result<GrowthBookClient, GrowthbookError> {
+        let client = GrowthBookClient::from_source(source.clone()).update_interval(Duration::from_secs(600)).build().await?;
+        // lets the refresh task run its first cycle
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        Ok(client)
+    }
+
+    fn fixture() -> (Vec<u8>, String) {
+        let body = std::fs::read("./tests/fixtures/webhook/payload.json").expect("Failed to read webhook fixture");
+        let signature = std::fs::read_to_string("./tests/fixtures/webhook/payload.json.sig").expect("Failed to read webhook signature");
+        (body, signature.trim().to_string())
+    }
+
+    #[tokio::test]
+    async fn should_refresh_now_and_report_changes() -> Result<(), Box<dyn std::error::Error>> {
+        let source = SequenceSource::new(vec![payload(false)]);
+        let client = client(&source).await?;
+        assert!(!client.refresh_now().await?);
+
+        source.set(payload(true));
+
+        assert!(client.refresh_now().await?);
+        assert!(client.is_on("webhook-flag", None));
+        assert!(!client.refresh_now().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refresh_on_signed_webhook() -> Result<(), Box<dyn std::error::Error>> {
+        let source = SequenceSource::new(vec![payload(false)]);
+        let handler = WebhookHandler::new(client(&source).await?, SECRET);
+        let (body, signature) = fixture();
+        source.set(payload(true));
+
+        let changed = handler.handle(Some(&signature), &body).await?;
+
+        assert!(changed);
+        assert!(handler.handle(Some(&format!("sha256={signature}")), &body).await.is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_webhook_with_invalid_signature() -> Result<(), Box<dyn std::error::Error>> {
+        let source = SequenceSource::new(vec![payload(false)]);
+        let client = client(&source).await?;
+        let handler = WebhookHandler::new(client.clone(), SECRET);
+        let (body, signature) = fixture();
+        source.set(payload(true));
+        let mut tampered = body.clone();
+        tampered.extend_from_slice(b" ");
+
+        for (signature, body) in [(None, body.clone()), (Some(signature.clone()), tampered), (Some(String::from("not-hex")), body.clone())] {
+            let error = handler.handle(signature.as_deref(), &body).await.expect_err("webhook should be rejected");
+            assert!(matches!(error.code, GrowthbookErrorCode::InvalidWebhookSignature));
+        }
+        let wrong_secret = WebhookHandler::new(client.clone(), "another-secret");
+        assert!(wrong_secret.verify(Some(&signature), &body).is_err());
+
+        assert!(client.is_off("webhook-flag", None));
+
+        Ok(())
+    }
+}